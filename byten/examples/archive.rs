@@ -1,24 +1,25 @@
 use std::ffi::CString;
 
-use byten::{Decode, DecodeOwned, Encode, Measure, SelfCodec, prelude::EncodeToVec as _, prim, util::{self, Convert}, var};
+use byten::{Decode, DecodeFrom, DecodeOwned, Encode, SelfCodec, prelude::EncodeToVec as _, prim, util::{self, Convert}, var};
 
 type U16BEAsUSize = Convert<prim::U16BE, usize>;
 
-#[derive(Debug, Encode, Measure, DecodeOwned)]
+#[derive(Debug, Encode, DecodeOwned, DecodeFrom)]
+#[byten(max_depth = 64)]
 pub struct Directory {
     pub name: CString,
     #[byten(var::Vec::<U16BEAsUSize, SelfCodec<_>>::default())]
     pub entries: Vec<Box<Entry>>,
 }
 
-#[derive(Debug, Encode, Measure, DecodeOwned)]
+#[derive(Debug, Encode, DecodeOwned, DecodeFrom)]
 pub struct File {
     pub name: CString,
     #[byten(util::Owned::<var::Slice<U16BEAsUSize>, Vec<u8>>::default())]
     pub content: Vec<u8>,
 }
 
-#[derive(Debug, Encode, Measure, DecodeOwned)]
+#[derive(Debug, Encode, DecodeOwned, DecodeFrom)]
 #[repr(u8)]
 pub enum Entry {
     File(File) = 1,
@@ -51,4 +52,8 @@ fn main() {
     let mut offset = 0;
     let decoded_dir = Directory::decode(&encoded, &mut offset).unwrap();
     println!("Decoded Directory: {:?}", decoded_dir);
+
+    let mut reader = std::io::BufReader::new(encoded.as_slice());
+    let read_dir = Directory::decode_from(&mut reader).unwrap();
+    println!("Read Directory: {:?}", read_dir);
 }
@@ -1,8 +1,8 @@
-use byten::{Decode, Encode, Measure, prelude::EncodeToVec as _, prim, util::Convert, var};
+use byten::{Decode, Encode, prelude::EncodeToVec as _, prim, util::Convert, var};
 
 type U16BEAsUSize = Convert<prim::U16BE, usize>;
 
-#[derive(Debug, Encode, Decode, Measure)]
+#[derive(Debug, Encode, Decode)]
 pub struct Person<'encoded> {
     #[byten(var::str::Str::<U16BEAsUSize>::default())]
     pub first_name: &'encoded str,
@@ -1,7 +1,7 @@
-use byten::{Encode, Measure, Decode, DecodeOwned, prim::U16BE, util, var};
+use byten::{Encode, Decode, DecodeOwned, prim::U16BE, util, var};
 use byten::prelude::EncodeToVec as _;
 
-#[derive(Debug, Encode, Measure, DecodeOwned)]
+#[derive(Debug, Encode, DecodeOwned)]
 pub struct IcmpHeader {
     pub icmp_type: u8,
     pub code: u8,
@@ -10,7 +10,7 @@ pub struct IcmpHeader {
     pub rest_of_header: [u8; 4],
 }
 
-#[derive(Debug, Encode, Measure, DecodeOwned)]
+#[derive(Debug, Encode, DecodeOwned)]
 pub struct IcmpPacket {
     pub header: IcmpHeader,
     #[byten(util::Owned::<var::Remaining, Vec<u8>>::default())]
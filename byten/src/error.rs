@@ -0,0 +1,418 @@
+use core::{convert::Infallible, num::TryFromIntError};
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::io;
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DecodeError {
+    #[error("Incomplete input")]
+    Incomplete { needed: Option<usize> },
+
+    #[error("Invalid discriminant")]
+    InvalidDiscriminant,
+
+    #[error("Invalid usize")]
+    InvalidUSize,
+
+    #[error("Data conversion failure")]
+    ConversionFailure,
+
+    #[error("Invalid data")]
+    InvalidData,
+
+    #[error("Codec failure")]
+    CodecFailure,
+
+    #[error("Length limit exceeded")]
+    LengthLimitExceeded,
+
+    #[error("Decode budget exceeded")]
+    BudgetExceeded,
+
+    #[error("Recursion depth limit exceeded")]
+    DepthLimitExceeded,
+
+    #[error("Non-canonical encoding")]
+    NonCanonical,
+
+    #[error("Zero value for a non-zero type")]
+    ZeroValue,
+
+    #[error("Magic or constant mismatch")]
+    MagicMismatch,
+
+    #[cfg(feature = "std")]
+    #[error("I/O: {0}")]
+    Io(io::Error),
+    
+    #[cfg(feature = "anyhow")]
+    #[error("Anyhow: {0}")]
+    Anyhow(#[from] anyhow::Error),
+
+    #[error("{0}")]
+    At(At),
+
+    #[cfg(feature = "alloc")]
+    #[error("{0}")]
+    Located(Box<Located>),
+}
+
+// What went wrong, without where. `Io` and `Anyhow` leave their source error on the `DecodeError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    Incomplete { needed: Option<usize> },
+    InvalidDiscriminant,
+    InvalidUSize,
+    ConversionFailure,
+    InvalidData,
+    CodecFailure,
+    LengthLimitExceeded,
+    BudgetExceeded,
+    DepthLimitExceeded,
+    NonCanonical,
+    ZeroValue,
+    MagicMismatch,
+    #[cfg(feature = "std")]
+    Io,
+    #[cfg(feature = "anyhow")]
+    Anyhow,
+}
+
+impl DecodeError {
+    // Records where the error occurred; an error that is already located keeps its innermost offset.
+    // Errors without a source error keep the offset inline, so this never allocates for them.
+    pub fn at(self, offset: usize) -> Self {
+        match self {
+            DecodeError::At(_) => self,
+            #[cfg(feature = "alloc")]
+            DecodeError::Located(mut located) => {
+                located.offset.get_or_insert(offset);
+                DecodeError::Located(located)
+            }
+            #[cfg(feature = "std")]
+            DecodeError::Io(_) => DecodeError::Located(Box::new(Located {
+                error: self,
+                offset: Some(offset),
+                path: Path::default(),
+            })),
+            #[cfg(feature = "anyhow")]
+            DecodeError::Anyhow(_) => DecodeError::Located(Box::new(Located {
+                error: self,
+                offset: Some(offset),
+                path: Path::default(),
+            })),
+            error => DecodeError::At(At {
+                kind: error.kind(),
+                offset,
+            }),
+        }
+    }
+
+    pub fn in_field(self, name: &'static str) -> Self {
+        self.in_segment(Segment::Field(name))
+    }
+
+    pub fn in_variant(self, name: &'static str) -> Self {
+        self.in_segment(Segment::Variant(name))
+    }
+
+    pub fn in_index(self, index: usize) -> Self {
+        self.in_segment(Segment::Index(index))
+    }
+
+    // Paths need `alloc`; without it the segment and type name are dropped and only the offset is kept.
+    pub fn in_segment(self, segment: Segment) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            let mut located = self.into_located();
+            located.path.segments.push(segment);
+            DecodeError::Located(located)
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = segment;
+            self
+        }
+    }
+
+    pub fn in_type(self, name: &'static str) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            let mut located = self.into_located();
+            located.path.ty = Some(name);
+            DecodeError::Located(located)
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = name;
+            self
+        }
+    }
+
+    // The underlying error, without any location attached.
+    pub fn kind(&self) -> DecodeErrorKind {
+        match self {
+            DecodeError::Incomplete { needed } => DecodeErrorKind::Incomplete { needed: *needed },
+            DecodeError::InvalidDiscriminant => DecodeErrorKind::InvalidDiscriminant,
+            DecodeError::InvalidUSize => DecodeErrorKind::InvalidUSize,
+            DecodeError::ConversionFailure => DecodeErrorKind::ConversionFailure,
+            DecodeError::InvalidData => DecodeErrorKind::InvalidData,
+            DecodeError::CodecFailure => DecodeErrorKind::CodecFailure,
+            DecodeError::LengthLimitExceeded => DecodeErrorKind::LengthLimitExceeded,
+            DecodeError::BudgetExceeded => DecodeErrorKind::BudgetExceeded,
+            DecodeError::DepthLimitExceeded => DecodeErrorKind::DepthLimitExceeded,
+            DecodeError::NonCanonical => DecodeErrorKind::NonCanonical,
+            DecodeError::ZeroValue => DecodeErrorKind::ZeroValue,
+            DecodeError::MagicMismatch => DecodeErrorKind::MagicMismatch,
+            #[cfg(feature = "std")]
+            DecodeError::Io(_) => DecodeErrorKind::Io,
+            #[cfg(feature = "anyhow")]
+            DecodeError::Anyhow(_) => DecodeErrorKind::Anyhow,
+            DecodeError::At(at) => at.kind,
+            #[cfg(feature = "alloc")]
+            DecodeError::Located(located) => located.error.kind(),
+        }
+    }
+
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            DecodeError::At(at) => Some(at.offset),
+            #[cfg(feature = "alloc")]
+            DecodeError::Located(located) => located.offset,
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            DecodeError::Located(located) => Some(&located.path),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn into_located(self) -> Box<Located> {
+        match self {
+            DecodeError::Located(located) => located,
+            DecodeError::At(at) => Box::new(Located {
+                error: at.error(),
+                offset: Some(at.offset),
+                path: Path::default(),
+            }),
+            error => Box::new(Located {
+                error,
+                offset: None,
+                path: Path::default(),
+            }),
+        }
+    }
+}
+
+// An error located by its offset alone. Only errors without a source error are kept here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct At {
+    kind: DecodeErrorKind,
+    offset: usize,
+}
+
+impl At {
+    pub fn kind(&self) -> DecodeErrorKind {
+        self.kind
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn error(&self) -> DecodeError {
+        match self.kind {
+            DecodeErrorKind::Incomplete { needed } => DecodeError::Incomplete { needed },
+            DecodeErrorKind::InvalidDiscriminant => DecodeError::InvalidDiscriminant,
+            DecodeErrorKind::InvalidUSize => DecodeError::InvalidUSize,
+            DecodeErrorKind::ConversionFailure => DecodeError::ConversionFailure,
+            DecodeErrorKind::InvalidData => DecodeError::InvalidData,
+            DecodeErrorKind::CodecFailure => DecodeError::CodecFailure,
+            DecodeErrorKind::LengthLimitExceeded => DecodeError::LengthLimitExceeded,
+            DecodeErrorKind::BudgetExceeded => DecodeError::BudgetExceeded,
+            DecodeErrorKind::DepthLimitExceeded => DecodeError::DepthLimitExceeded,
+            DecodeErrorKind::NonCanonical => DecodeError::NonCanonical,
+            DecodeErrorKind::ZeroValue => DecodeError::ZeroValue,
+            DecodeErrorKind::MagicMismatch => DecodeError::MagicMismatch,
+            #[cfg(feature = "std")]
+            DecodeErrorKind::Io => unreachable!("I/O errors are located with their source"),
+            #[cfg(feature = "anyhow")]
+            DecodeErrorKind::Anyhow => unreachable!("anyhow errors are located with their source"),
+        }
+    }
+}
+
+impl fmt::Display for At {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.error(), self.offset)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct Located {
+    pub error: DecodeError,
+    pub offset: Option<usize>,
+    pub path: Path,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Located {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        if self.path.ty.is_some() || !self.path.segments.is_empty() {
+            write!(f, " in {}", self.path)?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, " at offset {}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+// Segments are recorded innermost first, as the error propagates outwards.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub ty: Option<&'static str>,
+    pub segments: Vec<Segment>,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ty) = self.ty {
+            write!(f, "{}", ty)?;
+        }
+        for segment in self.segments.iter().rev() {
+            match segment {
+                Segment::Field(name) | Segment::Variant(name) => write!(f, ".{}", name)?,
+                Segment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment {
+    Field(&'static str),
+    Variant(&'static str),
+    Index(usize),
+}
+
+// Outcome of a decode attempt that tells running out of input apart from malformed input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress<T> {
+    Complete(T),
+    Incomplete { needed: Option<usize> },
+}
+
+impl<T> Progress<T> {
+    pub fn from_result(result: Result<T, DecodeError>) -> Result<Self, DecodeError> {
+        match result {
+            Ok(value) => Ok(Progress::Complete(value)),
+            Err(error) => match error.kind() {
+                DecodeErrorKind::Incomplete { needed } => Ok(Progress::Incomplete { needed }),
+                _ => Err(error),
+            },
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        matches!(self, Progress::Complete(_))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for DecodeError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => DecodeError::Incomplete { needed: None },
+            _ => DecodeError::Io(error),
+        }
+    }
+}
+
+impl From<Infallible> for DecodeError {
+    fn from(_: Infallible) -> Self {
+        unreachable!()
+    }
+}
+
+impl From<TryFromIntError> for DecodeError {
+    fn from(_: TryFromIntError) -> Self {
+        DecodeError::CodecFailure
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum EncodeError {
+    #[error("Buffer too small")]
+    BufferTooSmall,
+
+    #[error("Invalid usize")]
+    InvalidUSize,
+
+    #[error("Value out of range")]
+    OutOfRange,
+
+    #[error("Data conversion failure")]
+    CodecFailure,
+
+    #[cfg(feature = "std")]
+    #[error("I/O: {0}")]
+    Io(#[from] io::Error),
+
+    #[cfg(feature = "anyhow")]
+    #[error("Anyhow: {0}")]
+    Anyhow(#[from] anyhow::Error),
+}
+
+impl From<Infallible> for EncodeError {
+    fn from(_: Infallible) -> Self {
+        unreachable!()
+    }
+}
+
+impl From<TryFromIntError> for EncodeError {
+    fn from(_: TryFromIntError) -> Self {
+        EncodeError::CodecFailure
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_located() {
+        let error = DecodeError::InvalidData.at(7).at(3);
+        assert!(matches!(error, DecodeError::At(_)));
+        assert_eq!(error.kind(), DecodeErrorKind::InvalidData);
+        assert_eq!(error.offset(), Some(7));
+        assert_eq!(error.to_string(), "Invalid data at offset 7");
+
+        let error = error.in_index(2).in_field("items").in_type("List");
+        assert_eq!(error.kind(), DecodeErrorKind::InvalidData);
+        assert_eq!(error.offset(), Some(7));
+        assert_eq!(error.to_string(), "Invalid data in List.items[2] at offset 7");
+
+        let error = DecodeError::from(io::Error::other("reset")).at(5);
+        assert_eq!(error.kind(), DecodeErrorKind::Io);
+        assert_eq!(error.offset(), Some(5));
+        assert_eq!(error.to_string(), "I/O: reset at offset 5");
+    }
+}
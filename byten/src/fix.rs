@@ -1,4 +1,21 @@
-use crate::{DecodeError, Decoder, EncodeError, Encoder, FixedMeasurer, Measurer};
+#[cfg(feature = "std")]
+use std::io::Read;
+
+use crate::{DecodeError, Decoder, EncodeError, Encoder, FixedMeasurer, Sink, limit::Budget};
+
+#[cfg(feature = "std")]
+use crate::ReadDecoder;
+
+// Builds an array from a fallible per-index decode, stopping at the first error.
+pub(crate) fn try_from_fn<T, const N: usize>(mut decode: impl FnMut(usize) -> Result<T, DecodeError>) -> Result<[T; N], DecodeError> {
+    let mut result = Ok(());
+    let items: [Option<T>; N] = core::array::from_fn(|index| {
+        result.as_ref().ok()?;
+        decode(index).map_err(|error| result = Err(error)).ok()
+    });
+    result?;
+    Ok(items.map(|item| item.expect("Every item decoded")))
+}
 
 pub struct Array<Item, const N: usize>(pub Item);
 
@@ -22,13 +39,30 @@ where
     type Decoded = [Item::Decoded; N];
 
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, DecodeError> {
-        let mut array: heapless::Vec<Item::Decoded, N> = heapless::Vec::new();
-        for _ in 0..N {
-            let item = self.0.decode(encoded, offset)?;
-            array.push(item).unwrap_or_else(|_| panic!("unexpected heapless vec overflow"));
-        }
-        let array = array.into_array().unwrap_or_else(|_| panic!("unexpected heapless vec underflow"));
-        Ok(array)
+        self.decode_with(encoded, offset, &mut Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut Budget) -> Result<Self::Decoded, DecodeError> {
+        try_from_fn(|index| {
+            let start = *offset;
+            self.0.decode_with(encoded, offset, budget).map_err(|error| error.at(start).in_index(index))
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Item, const N: usize> ReadDecoder for Array<Item, N>
+where
+    Item: ReadDecoder,
+{
+    type Decoded = [Item::Decoded; N];
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, DecodeError> {
+        self.decode_from_with(reader, &mut Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut Budget) -> Result<Self::Decoded, DecodeError> {
+        try_from_fn(|index| self.0.decode_from_with(reader, budget).map_err(|error| error.in_index(index)))
     }
 }
 
@@ -39,9 +73,9 @@ where
 {
     type Decoded = [Item::Decoded; N];
 
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError> {
+    fn encode<S: Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), EncodeError> {
         for item in decoded.iter() {
-            self.0.encode(item, encoded, offset)?;
+            self.0.encode(item, sink)?;
         }
         Ok(())
     }
@@ -57,18 +91,175 @@ where
     }
 }
 
-impl<Item, const N: usize> Measurer for Array<Item, N>
+// A fixed signature such as a file format's magic bytes: written on encode, checked on decode
+// with `DecodeError::MagicMismatch`, and decoded into `()` so it needs no storage.
+pub struct Magic {
+    pub bytes: &'static [u8],
+}
+
+impl Magic {
+    pub const fn codec(bytes: &'static [u8]) -> Self {
+        Self { bytes }
+    }
+}
+
+impl<'encoded, 'decoded> Decoder<'encoded, 'decoded> for Magic {
+    type Decoded = ();
+
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, DecodeError> {
+        let start = *offset;
+        if crate::take_bytes(encoded, offset, self.bytes.len())? != self.bytes {
+            *offset = start;
+            return Err(DecodeError::MagicMismatch);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl ReadDecoder for Magic {
+    type Decoded = ();
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, DecodeError> {
+        let mut buffer = [0u8; 32];
+        for chunk in self.bytes.chunks(buffer.len()) {
+            let buffer = &mut buffer[..chunk.len()];
+            reader.read_exact(buffer)?;
+            if buffer != chunk {
+                return Err(DecodeError::MagicMismatch);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Encoder for Magic {
+    type Decoded = ();
+
+    fn encode<S: Sink + ?Sized>(&self, _decoded: &Self::Decoded, sink: &mut S) -> Result<(), EncodeError> {
+        sink.write(self.bytes)
+    }
+}
+
+impl FixedMeasurer for Magic {
+    fn measure_fixed(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+// A must-be-constant value such as a format version, encoded with `codec` like any other value
+// but decoded into `()` after checking it equals `value`.
+pub struct Const<Codec, Value> {
+    pub codec: Codec,
+    pub value: Value,
+}
+
+impl<Codec, Value> Const<Codec, Value> {
+    pub const fn codec(codec: Codec, value: Value) -> Self {
+        Self { codec, value }
+    }
+}
+
+impl<'encoded, 'decoded, Codec, Value> Decoder<'encoded, 'decoded> for Const<Codec, Value>
 where
-    Item: Measurer,
-    Item::Decoded: Sized,
+    Codec: Decoder<'encoded, 'decoded>,
+    Codec::Decoded: PartialEq<Value>,
 {
-    type Decoded = [Item::Decoded; N];
+    type Decoded = ();
 
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, EncodeError> {
-        let mut total = 0;
-        for item in decoded.iter() {
-            total += self.0.measure(item)?;
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, DecodeError> {
+        self.decode_with(encoded, offset, &mut Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut Budget) -> Result<Self::Decoded, DecodeError> {
+        let start = *offset;
+        if self.codec.decode_with(encoded, offset, budget)? != self.value {
+            *offset = start;
+            return Err(DecodeError::MagicMismatch);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Codec, Value> ReadDecoder for Const<Codec, Value>
+where
+    Codec: ReadDecoder,
+    Codec::Decoded: PartialEq<Value>,
+{
+    type Decoded = ();
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, DecodeError> {
+        self.decode_from_with(reader, &mut Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut Budget) -> Result<Self::Decoded, DecodeError> {
+        if self.codec.decode_from_with(reader, budget)? != self.value {
+            return Err(DecodeError::MagicMismatch);
         }
-        Ok(total)
+        Ok(())
+    }
+}
+
+impl<Codec, Value> Encoder for Const<Codec, Value>
+where
+    Codec: Encoder<Decoded = Value>,
+{
+    type Decoded = ();
+
+    fn encode<S: Sink + ?Sized>(&self, _decoded: &Self::Decoded, sink: &mut S) -> Result<(), EncodeError> {
+        self.codec.encode(&self.value, sink)
+    }
+}
+
+impl<Codec, Value> FixedMeasurer for Const<Codec, Value>
+where
+    Codec: FixedMeasurer<Decoded = Value>,
+{
+    fn measure_fixed(&self) -> usize {
+        self.codec.measure_fixed()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use crate::{ReadDecoder as _, prim};
+
+    use super::*;
+
+    #[test]
+    fn test_magic() {
+        let magic = Magic::codec(b"\x89PNG\r\n\x1a\n");
+        let mut offset = 0;
+        magic.decode(b"\x89PNG\r\n\x1a\n\x00", &mut offset).expect("Decoding failed");
+        assert_eq!(offset, 8);
+        assert_eq!(magic.measure_fixed(), 8);
+
+        let mut offset = 0;
+        let error = magic.decode(b"\x89PNX\r\n\x1a\n", &mut offset).expect_err("Wrong magic decoded");
+        assert!(matches!(error, DecodeError::MagicMismatch));
+        assert_eq!(offset, 0);
+        assert!(matches!(magic.decode(b"\x89PN", &mut 0), Err(DecodeError::Incomplete { needed: Some(5) })));
+
+        let long = Magic::codec(&[0xa5; 40]);
+        long.decode_from(&mut [0xa5; 40].as_slice()).expect("Reading failed");
+        let mut wrong = [0xa5; 40];
+        wrong[35] = 0;
+        assert!(matches!(long.decode_from(&mut wrong.as_slice()), Err(DecodeError::MagicMismatch)));
+    }
+
+    #[test]
+    fn test_const() {
+        let version = Const::codec(prim::U16BE, 2);
+        let mut encoded = [0u8; 2];
+        version.encode(&(), &mut crate::sink::SliceSink::new(&mut encoded, &mut 0)).expect("Encoding failed");
+        assert_eq!(encoded, [0x00, 0x02]);
+        assert_eq!(version.measure_fixed(), 2);
+        version.decode(&encoded, &mut 0).expect("Decoding failed");
+
+        let mut offset = 0;
+        assert!(matches!(version.decode(&[0x00, 0x03], &mut offset), Err(DecodeError::MagicMismatch)));
+        assert_eq!(offset, 0);
+        assert!(matches!(version.decode_from(&mut [0x01, 0x02].as_slice()), Err(DecodeError::MagicMismatch)));
     }
 }
@@ -0,0 +1,220 @@
+use core::marker::PhantomData;
+
+use bytes::{Buf, BytesMut};
+
+use crate::{DecodeError, DecodeOwned, Decoder, Encode, EncodeError, Encoder, Progress};
+
+// Same default as `tokio_util::codec::LengthDelimitedCodec`, so a hostile length prefix cannot make
+// the read buffer grow without bound.
+pub const DEFAULT_MAX_FRAME_LENGTH: usize = 8 * 1024 * 1024;
+
+pub struct FramedCodec<Length, T> {
+    pub length: Length,
+    pub max_frame_length: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<Length, T> FramedCodec<Length, T> {
+    pub const fn codec(length: Length) -> Self {
+        Self {
+            length,
+            max_frame_length: DEFAULT_MAX_FRAME_LENGTH,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn with_max_frame_length(self, max_frame_length: usize) -> Self {
+        Self { max_frame_length, ..self }
+    }
+}
+
+impl<Length, T> Default for FramedCodec<Length, T>
+where
+    Length: Default,
+{
+    fn default() -> Self { Self::codec(Length::default()) }
+}
+
+impl<Length, T> tokio_util::codec::Decoder for FramedCodec<Length, T>
+where
+    Length: for<'encoded> Decoder<'encoded, 'encoded, Decoded = usize>,
+    T: DecodeOwned,
+{
+    type Item = T;
+    type Error = DecodeError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let mut offset = 0;
+        let size = match self.length.try_decode(src, &mut offset)? {
+            Progress::Complete(size) => size,
+            Progress::Incomplete { .. } => return Ok(None),
+        };
+        if size > self.max_frame_length {
+            return Err(DecodeError::LengthLimitExceeded);
+        }
+        let end = offset.checked_add(size).ok_or(DecodeError::InvalidUSize)?;
+        if src.len() < end {
+            src.reserve(end - src.len());
+            return Ok(None);
+        }
+
+        let frame = src.split_to(end);
+        let item = T::decode(&frame, &mut offset)?;
+        if offset != end {
+            return Err(DecodeError::InvalidData);
+        }
+        Ok(Some(item))
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(item) => Ok(Some(item)),
+            None if src.has_remaining() => Err(DecodeError::Incomplete { needed: None }),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<Length, T> tokio_util::codec::Encoder<&T> for FramedCodec<Length, T>
+where
+    Length: Encoder<Decoded = usize>,
+    T: Encode,
+{
+    type Error = EncodeError;
+
+    // The body is encoded once and its length appended after it, then rotated to the front, which
+    // works for length codecs of any width at the cost of moving the frame's bytes.
+    fn encode(&mut self, item: &T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let start = dst.len();
+        let encoded = item.encode(dst).and_then(|()| {
+            let body_end = dst.len();
+            self.length.encode(&(body_end - start), dst)?;
+            Ok(dst.len() - body_end)
+        });
+        match encoded {
+            Ok(prefix) => {
+                dst[start..].rotate_right(prefix);
+                Ok(())
+            },
+            Err(error) => {
+                dst.truncate(start);
+                Err(error)
+            },
+        }
+    }
+}
+
+impl<Length, T> tokio_util::codec::Encoder<T> for FramedCodec<Length, T>
+where
+    Length: Encoder<Decoded = usize>,
+    T: Encode,
+{
+    type Error = EncodeError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        tokio_util::codec::Encoder::<&T>::encode(self, &item, dst)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::CString;
+
+    use futures_util::{SinkExt as _, StreamExt as _};
+    use tokio_util::codec::{Decoder as _, FramedRead, FramedWrite};
+
+    use crate::{prim, util::Convert, var};
+
+    use super::*;
+
+    type U32BEAsUSize = Convert<prim::U32BE, usize>;
+
+    fn messages() -> Vec<CString> {
+        ["hello", "", "framed byten"]
+            .into_iter()
+            .map(|message| CString::new(message).expect("Message contains a nul byte"))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_duplex_roundtrip() {
+        let (client, server) = tokio::io::duplex(4);
+        let mut writer = FramedWrite::new(client, FramedCodec::<U32BEAsUSize, CString>::default());
+        let mut reader = FramedRead::new(server, FramedCodec::<U32BEAsUSize, CString>::default());
+
+        let sent = messages();
+        let expected = sent.clone();
+        let write = tokio::spawn(async move {
+            for message in sent {
+                writer.send(message).await.expect("Sending failed");
+            }
+        });
+
+        for message in expected {
+            let received = reader.next().await.expect("Stream ended").expect("Receiving failed");
+            assert_eq!(received, message);
+        }
+        write.await.expect("Writer task panicked");
+        assert!(reader.next().await.is_none());
+    }
+
+    #[test]
+    fn test_partial_frames() {
+        let mut codec = FramedCodec::<var::USizeBE, CString>::default();
+        let mut encoded = BytesMut::new();
+        for message in messages() {
+            tokio_util::codec::Encoder::encode(&mut codec, &message, &mut encoded).expect("Encoding failed");
+        }
+
+        let mut src = BytesMut::new();
+        let mut received = Vec::new();
+        for &byte in encoded.iter() {
+            src.extend_from_slice(&[byte]);
+            while let Some(message) = codec.decode(&mut src).expect("Decoding failed") {
+                received.push(message);
+            }
+        }
+        assert_eq!(received, messages());
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn test_truncated_frame_at_eof() {
+        let mut codec = FramedCodec::<U32BEAsUSize, CString>::default();
+        let mut src = BytesMut::from(&[0u8, 0, 0, 3, b'a'][..]);
+        assert!(codec.decode(&mut src).expect("Decoding failed").is_none());
+        assert!(matches!(codec.decode_eof(&mut src), Err(DecodeError::Incomplete { .. })));
+    }
+
+    #[test]
+    fn test_max_frame_length() {
+        let mut codec = FramedCodec::<U32BEAsUSize, CString>::default();
+        let mut src = BytesMut::from(&[0xffu8, 0xff, 0xff, 0xff, b'a'][..]);
+        assert!(matches!(codec.decode(&mut src), Err(DecodeError::LengthLimitExceeded)));
+
+        let mut codec = codec.with_max_frame_length(16);
+        let mut src = BytesMut::from(&[0u8, 0, 0, 17][..]);
+        assert!(matches!(codec.decode(&mut src), Err(DecodeError::LengthLimitExceeded)));
+
+        let mut src = BytesMut::from(&[0u8, 0, 0, 16, b'a'][..]);
+        assert!(codec.decode(&mut src).expect("Decoding failed").is_none());
+        assert!(src.capacity() >= 20);
+    }
+
+    #[test]
+    fn test_encoded_frame() {
+        let mut codec = FramedCodec::<var::USizeBE, CString>::default();
+        let mut dst = BytesMut::from(&b"prior"[..]);
+        let message = CString::new(vec![b'x'; 200]).expect("Message contains a nul byte");
+        tokio_util::codec::Encoder::encode(&mut codec, &message, &mut dst).expect("Encoding failed");
+        assert_eq!(&dst[..8], b"prior\x81\x49x");
+        assert_eq!(dst.len(), 5 + 2 + 201);
+    }
+
+    #[test]
+    fn test_frame_with_trailing_bytes() {
+        let mut codec = FramedCodec::<U32BEAsUSize, CString>::default();
+        let mut src = BytesMut::from(&[0u8, 0, 0, 3, b'a', 0, b'b'][..]);
+        assert!(matches!(codec.decode(&mut src), Err(DecodeError::InvalidData)));
+    }
+}
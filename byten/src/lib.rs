@@ -1,86 +1,52 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod error;
 pub mod fix;
+#[cfg(feature = "tokio-util")]
+pub mod framed;
+pub mod limit;
 pub mod prelude;
 pub mod prim;
+pub mod sink;
 pub mod util;
 pub mod var;
 
-use std::{convert::Infallible, ffi::{CStr, CString}, num::TryFromIntError, ops::Deref};
-
-#[cfg(feature = "derive")]
-pub use byten_derive::{Decode, DecodeOwned, Encode, Measure, MeasureFixed};
-
-use thiserror::Error;
-
-#[derive(Error, Debug)]
-pub enum DecodeError {
-    #[error("End of file reached")]
-    EOF,
-
-    #[error("Invalid discriminant")]
-    InvalidDiscriminant,
+use core::{any::TypeId, ffi::CStr, num::{NonZeroI8, NonZeroU8, Wrapping}, ops::Deref};
 
-    #[error("Invalid usize")]
-    InvalidUSize,
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, ffi::CString};
 
-    #[error("Data conversion failure")]
-    ConversionFailure,
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
 
-    #[error("Invalid data")]
-    InvalidData,
-
-    #[error("Codec failure")]
-    CodecFailure,
-    
-    #[cfg(feature = "anyhow")]
-    #[error("Anyhow: {0}")]
-    Anyhow(#[from] anyhow::Error),
-}
-
-impl From<Infallible> for DecodeError {
-    fn from(_: Infallible) -> Self {
-        unreachable!()
-    }
-}
-
-impl From<TryFromIntError> for DecodeError {
-    fn from(_: TryFromIntError) -> Self {
-        DecodeError::CodecFailure
-    }
-}
+#[cfg(feature = "derive")]
+pub use byten_derive::{Decode, DecodeOwned, Encode, Measure, MeasureFixed};
 
-#[derive(Error, Debug)]
-pub enum EncodeError {
-    #[error("Buffer too small")]
-    BufferTooSmall,
+#[cfg(all(feature = "derive", feature = "std"))]
+pub use byten_derive::DecodeFrom;
 
-    #[error("Invalid usize")]
-    InvalidUSize,
+pub use error::{DecodeError, DecodeErrorKind, EncodeError, Progress};
 
-    #[error("Data conversion failure")]
-    CodecFailure,
+use limit::Budget;
 
-    #[cfg(feature = "anyhow")]
-    #[error("Anyhow: {0}")]
-    Anyhow(#[from] anyhow::Error),
-}
+// codec traits
 
-impl From<Infallible> for EncodeError {
-    fn from(_: Infallible) -> Self {
-        unreachable!()
-    }
+pub trait Sink {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError>;
 }
 
-impl From<TryFromIntError> for EncodeError {
-    fn from(_: TryFromIntError) -> Self {
-        EncodeError::CodecFailure
+impl<S: Sink + ?Sized> Sink for &mut S {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        (**self).write(bytes)
     }
 }
 
-// codec traits
-
 pub trait Encoder {
     type Decoded: ?Sized;
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError>;
+    fn encode<S: Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), EncodeError>;
 }
 
 impl<T, Ref> Encoder for Ref
@@ -89,14 +55,41 @@ where
     Ref: Deref<Target = T>,
 {
     type Decoded = T::Decoded;
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError> {
-        self.deref().encode(decoded, encoded, offset)
+    fn encode<S: Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), EncodeError> {
+        self.deref().encode(decoded, sink)
     }
 }
 
+#[cfg(feature = "std")]
+pub trait WriteEncoder: Encoder {
+    fn encode_to<W: Write + ?Sized>(&self, decoded: &Self::Decoded, writer: &mut W) -> Result<(), EncodeError> {
+        self.encode(decoded, &mut sink::WriteSink::new(writer))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Encoder + ?Sized> WriteEncoder for T {}
+
 pub trait Decoder<'encoded, 'decoded> {
     type Decoded: 'decoded;
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, DecodeError>;
+
+    // Charges what the decoded value allocates against `budget`. Codecs that allocate or wrap other
+    // codecs override this; the default suits codecs that do neither.
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut Budget) -> Result<Self::Decoded, DecodeError> {
+        let _ = budget;
+        self.decode(encoded, offset)
+    }
+
+    // Leaves `offset` untouched when more input is needed, so the call can be retried once it arrives.
+    fn try_decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Progress<Self::Decoded>, DecodeError> {
+        let start = *offset;
+        let progress = Progress::from_result(self.decode(encoded, offset));
+        if let Ok(Progress::Incomplete { .. }) = progress {
+            *offset = start;
+        }
+        progress
+    }
 }
 
 impl<'encoded, 'decoded, T, Ref> Decoder<'encoded, 'decoded> for Ref
@@ -108,24 +101,22 @@ where
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, DecodeError> {
         self.deref().decode(encoded, offset)
     }
-}
 
-pub trait Measurer {
-    type Decoded: ?Sized;
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, EncodeError>;
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut Budget) -> Result<Self::Decoded, DecodeError> {
+        self.deref().decode_with(encoded, offset, budget)
+    }
 }
 
-impl<T, Ref> Measurer for Ref
-where
-    T: Measurer,
-    Ref: Deref<Target = T>,
-{
-    type Decoded = T::Decoded;
+pub trait Measurer: Encoder {
     fn measure(&self, decoded: &Self::Decoded) -> Result<usize, EncodeError> {
-        self.deref().measure(decoded)
+        let mut sink = sink::CountingSink::new();
+        self.encode(decoded, &mut sink)?;
+        Ok(sink.count())
     }
 }
 
+impl<T: Encoder + ?Sized> Measurer for T {}
+
 pub trait FixedMeasurer: Measurer {
     fn measure_fixed(&self) -> usize;
 }
@@ -140,39 +131,173 @@ where
     }
 }
 
+#[cfg(feature = "std")]
+pub trait ReadDecoder {
+    type Decoded;
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, DecodeError>;
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut Budget) -> Result<Self::Decoded, DecodeError> {
+        let _ = budget;
+        self.decode_from(reader)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, Ref> ReadDecoder for Ref
+where
+    T: ReadDecoder,
+    Ref: Deref<Target = T>,
+{
+    type Decoded = T::Decoded;
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, DecodeError> {
+        self.deref().decode_from(reader)
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut Budget) -> Result<Self::Decoded, DecodeError> {
+        self.deref().decode_from_with(reader, budget)
+    }
+}
+
 // self-codecs
 
+// Every type that decodes from any input lifetime is owned, derived or not.
 pub trait DecodeOwned: Sized + for<'encoded> Decode<'encoded> {
     fn decode_owned(encoded: &[u8], offset: &mut usize) -> Result<Self, DecodeError> {
         <Self as Decode>::decode(encoded, offset)
     }
 }
 
+impl<T> DecodeOwned for T where T: for<'encoded> Decode<'encoded> {}
+
 pub trait Decode<'encoded> {
     fn decode(encoded: &'encoded [u8], offset: &mut usize) -> Result<Self, DecodeError>
     where
         Self: Sized;
+
+    // See `Decoder::decode_with`; derived impls pass `budget` on to every field.
+    fn decode_with(encoded: &'encoded [u8], offset: &mut usize, budget: &mut Budget) -> Result<Self, DecodeError>
+    where
+        Self: Sized,
+    {
+        let _ = budget;
+        Self::decode(encoded, offset)
+    }
+
+    fn try_decode(encoded: &'encoded [u8], offset: &mut usize) -> Result<Progress<Self>, DecodeError>
+    where
+        Self: Sized,
+    {
+        let start = *offset;
+        let progress = Progress::from_result(Self::decode(encoded, offset));
+        if let Ok(Progress::Incomplete { .. }) = progress {
+            *offset = start;
+        }
+        progress
+    }
+
+    fn decode_array<const N: usize>(encoded: &'encoded [u8], offset: &mut usize) -> Result<[Self; N], DecodeError>
+    where
+        Self: Sized,
+    {
+        Self::decode_array_with(encoded, offset, &mut Budget::new())
+    }
+
+    // Backs `[Self; N]`; `u8` overrides it with a single bulk copy.
+    fn decode_array_with<const N: usize>(encoded: &'encoded [u8], offset: &mut usize, budget: &mut Budget) -> Result<[Self; N], DecodeError>
+    where
+        Self: Sized,
+    {
+        fix::try_from_fn(|index| {
+            let start = *offset;
+            Self::decode_with(encoded, offset, budget).map_err(|error| error.at(start).in_index(index))
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+pub trait DecodeFrom: Sized {
+    fn decode_from<R: Read + ?Sized>(reader: &mut R) -> Result<Self, DecodeError>;
+
+    fn decode_from_with<R: Read + ?Sized>(reader: &mut R, budget: &mut Budget) -> Result<Self, DecodeError> {
+        let _ = budget;
+        Self::decode_from(reader)
+    }
+
+    fn decode_array_from<R: Read + ?Sized, const N: usize>(reader: &mut R) -> Result<[Self; N], DecodeError> {
+        Self::decode_array_from_with(reader, &mut Budget::new())
+    }
+
+    fn decode_array_from_with<R: Read + ?Sized, const N: usize>(reader: &mut R, budget: &mut Budget) -> Result<[Self; N], DecodeError> {
+        fix::try_from_fn(|index| Self::decode_from_with(reader, budget).map_err(|error| error.in_index(index)))
+    }
 }
 
 pub trait Encode {
-    fn encode(&self, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError>;
+    fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError>;
+
+    fn encode_slice<S: Sink + ?Sized>(items: &[Self], sink: &mut S) -> Result<(), EncodeError>
+    where
+        Self: Sized,
+    {
+        for item in items {
+            item.encode(sink)?;
+        }
+        Ok(())
+    }
 }
 
-pub trait Measure {
-    fn measure(&self) -> Result<usize, EncodeError>;
+#[cfg(feature = "std")]
+pub trait EncodeTo: Encode {
+    fn encode_to<W: Write + ?Sized>(&self, writer: &mut W) -> Result<(), EncodeError> {
+        self.encode(&mut sink::WriteSink::new(writer))
+    }
 }
 
+#[cfg(feature = "std")]
+impl<T: Encode + ?Sized> EncodeTo for T {}
+
+pub trait Measure: Encode {
+    fn measure(&self) -> Result<usize, EncodeError> {
+        let mut sink = sink::CountingSink::new();
+        self.encode(&mut sink)?;
+        Ok(sink.count())
+    }
+}
+
+impl<T: Encode + ?Sized> Measure for T {}
+
 pub trait MeasureFixed: Measure {
     fn measure_fixed() -> usize;
 }
 
 pub struct SelfCodec<T> {
+    // The limit and the `TypeId` its nesting is counted under, which only a `'static` `T` has.
+    max_depth: Option<(usize, fn() -> TypeId)>,
     _marker: core::marker::PhantomData<T>,
 }
 
 impl<T> SelfCodec<T> {
     pub const fn codec() -> Self {
         SelfCodec {
+            max_depth: None,
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    pub const fn max_depth(&self) -> Option<usize> {
+        match self.max_depth {
+            Some((max_depth, _)) => Some(max_depth),
+            None => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: 'static> SelfCodec<T> {
+    // Nesting of this codec is counted apart from every other guarded codec or type.
+    pub const fn with_max_depth(self, max_depth: usize) -> Self {
+        SelfCodec {
+            max_depth: Some((max_depth, TypeId::of::<Self>)),
             _marker: core::marker::PhantomData,
         }
     }
@@ -189,21 +314,33 @@ where
 {
     type Decoded = T;
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, DecodeError> {
-        T::decode(encoded, offset)
+        self.decode_with(encoded, offset, &mut Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut Budget) -> Result<Self::Decoded, DecodeError> {
+        #[cfg(feature = "std")]
+        let _depth = self.max_depth.map(|(max_depth, key)| limit::enter_key(key(), max_depth)).transpose()?;
+        T::decode_with(encoded, offset, budget)
     }
 }
 
-impl<T: Encode> Encoder for SelfCodec<T> {
+#[cfg(feature = "std")]
+impl<T: DecodeFrom> ReadDecoder for SelfCodec<T> {
     type Decoded = T;
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError> {
-        decoded.encode(encoded, offset)
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, DecodeError> {
+        self.decode_from_with(reader, &mut Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut Budget) -> Result<Self::Decoded, DecodeError> {
+        let _depth = self.max_depth.map(|(max_depth, key)| limit::enter_key(key(), max_depth)).transpose()?;
+        T::decode_from_with(reader, budget)
     }
 }
 
-impl<T: Measure> Measurer for SelfCodec<T> {
+impl<T: Encode> Encoder for SelfCodec<T> {
     type Decoded = T;
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, EncodeError> {
-        decoded.measure()
+    fn encode<S: Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), EncodeError> {
+        decoded.encode(sink)
     }
 }
 
@@ -213,27 +350,54 @@ impl<T: MeasureFixed> FixedMeasurer for SelfCodec<T> {
     }
 }
 
+// Borrows the next `size` bytes and advances `offset`, trusting neither of them.
+pub(crate) fn take_bytes<'encoded>(encoded: &'encoded [u8], offset: &mut usize, size: usize) -> Result<&'encoded [u8], DecodeError> {
+    let end = offset.checked_add(size).ok_or(DecodeError::InvalidUSize)?;
+    match encoded.get(*offset..end) {
+        Some(bytes) => {
+            *offset = end;
+            Ok(bytes)
+        },
+        None => Err(DecodeError::Incomplete { needed: Some(end - encoded.len()) }),
+    }
+}
+
 // very basic implementations
 
 impl Decode<'_> for u8 {
     fn decode(encoded: &[u8], offset: &mut usize) -> Result<Self, DecodeError> {
-        if *offset >= encoded.len() {
-            return Err(DecodeError::EOF);
-        }
-        let value = encoded[*offset];
-        *offset += 1;
+        let [value] = Self::decode_array(encoded, offset)?;
+        Ok(value)
+    }
+
+    fn decode_array_with<const N: usize>(encoded: &[u8], offset: &mut usize, _budget: &mut Budget) -> Result<[Self; N], DecodeError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(take_bytes(encoded, offset, N)?);
+        Ok(array)
+    }
+}
+
+#[cfg(feature = "std")]
+impl DecodeFrom for u8 {
+    fn decode_from<R: Read + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        let [value] = Self::decode_array_from(reader)?;
         Ok(value)
     }
+
+    fn decode_array_from_with<R: Read + ?Sized, const N: usize>(reader: &mut R, _budget: &mut Budget) -> Result<[Self; N], DecodeError> {
+        let mut array = [0u8; N];
+        reader.read_exact(&mut array)?;
+        Ok(array)
+    }
 }
 
 impl Encode for u8 {
-    fn encode(&self, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError> {
-        if *offset >= encoded.len() {
-            return Err(EncodeError::BufferTooSmall);
-        }
-        encoded[*offset] = *self;
-        *offset += 1;
-        Ok(())
+    fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError> {
+        sink.write(&[*self])
+    }
+
+    fn encode_slice<S: Sink + ?Sized>(items: &[Self], sink: &mut S) -> Result<(), EncodeError> {
+        sink.write(items)
     }
 }
 
@@ -241,39 +405,35 @@ impl MeasureFixed for u8 {
     fn measure_fixed() -> usize { 1 }
 }
 
-impl Measure for u8 {
-    fn measure(&self) -> Result<usize, EncodeError> { Ok(Self::measure_fixed()) }
-}
+impl<'encoded, T: Decode<'encoded>, const N: usize> Decode<'encoded> for [T; N] {
+    fn decode(encoded: &'encoded [u8], offset: &mut usize) -> Result<Self, DecodeError> {
+        T::decode_array(encoded, offset)
+    }
 
-impl<const N: usize> Decode<'_> for [u8; N] {
-    fn decode(encoded: &[u8], offset: &mut usize) -> Result<Self, DecodeError> {
-        if *offset + N > encoded.len() {
-            return Err(DecodeError::EOF);
-        }
-        let mut array = [0u8; N];
-        array.copy_from_slice(&encoded[*offset..*offset + N]);
-        *offset += N;
-        Ok(array)
+    fn decode_with(encoded: &'encoded [u8], offset: &mut usize, budget: &mut Budget) -> Result<Self, DecodeError> {
+        T::decode_array_with(encoded, offset, budget)
     }
 }
 
-impl<const N: usize> Encode for [u8; N] {
-    fn encode(&self, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError> {
-        if *offset + N > encoded.len() {
-            return Err(EncodeError::BufferTooSmall);
-        }
-        encoded[*offset..*offset + N].copy_from_slice(self);
-        *offset += N;
-        Ok(())
+#[cfg(feature = "std")]
+impl<T: DecodeFrom, const N: usize> DecodeFrom for [T; N] {
+    fn decode_from<R: Read + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        T::decode_array_from(reader)
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(reader: &mut R, budget: &mut Budget) -> Result<Self, DecodeError> {
+        T::decode_array_from_with(reader, budget)
     }
 }
 
-impl<const N: usize> MeasureFixed for [u8; N] {
-    fn measure_fixed() -> usize { N }
+impl<T: Encode, const N: usize> Encode for [T; N] {
+    fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError> {
+        T::encode_slice(self, sink)
+    }
 }
 
-impl<const N: usize> Measure for [u8; N] {
-    fn measure(&self) -> Result<usize, EncodeError> { Ok(Self::measure_fixed()) }
+impl<T: MeasureFixed, const N: usize> MeasureFixed for [T; N] {
+    fn measure_fixed() -> usize { N * T::measure_fixed() }
 }
 
 impl Decode<'_> for bool {
@@ -287,10 +447,22 @@ impl Decode<'_> for bool {
     }
 }
 
+#[cfg(feature = "std")]
+impl DecodeFrom for bool {
+    fn decode_from<R: Read + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        let byte = u8::decode_from(reader)?;
+        match byte {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DecodeError::InvalidData),
+        }
+    }
+}
+
 impl Encode for bool {
-    fn encode(&self, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError> {
+    fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError> {
         let byte = if *self { 1u8 } else { 0u8 };
-        byte.encode(encoded, offset)
+        byte.encode(sink)
     }
 }
 
@@ -298,23 +470,189 @@ impl MeasureFixed for bool {
     fn measure_fixed() -> usize { 1 }
 }
 
-impl Measure for bool {
-    fn measure(&self) -> Result<usize, EncodeError> { Ok(Self::measure_fixed()) }
+impl Decode<'_> for i8 {
+    fn decode(encoded: &[u8], offset: &mut usize) -> Result<Self, DecodeError> {
+        Ok(u8::decode(encoded, offset)? as i8)
+    }
+}
+
+#[cfg(feature = "std")]
+impl DecodeFrom for i8 {
+    fn decode_from<R: Read + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        Ok(u8::decode_from(reader)? as i8)
+    }
+}
+
+impl Encode for i8 {
+    fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError> {
+        (*self as u8).encode(sink)
+    }
+}
+
+impl MeasureFixed for i8 {
+    fn measure_fixed() -> usize { 1 }
+}
+
+macro_rules! impl_non_zero_byte {
+    ($($t:ty => $int:ty),+ $(,)?) => {
+        $(
+            impl Decode<'_> for $t {
+                fn decode(encoded: &[u8], offset: &mut usize) -> Result<Self, DecodeError> {
+                    <$t>::new(<$int>::decode(encoded, offset)?).ok_or(DecodeError::ZeroValue)
+                }
+            }
+
+            #[cfg(feature = "std")]
+            impl DecodeFrom for $t {
+                fn decode_from<R: Read + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+                    <$t>::new(<$int>::decode_from(reader)?).ok_or(DecodeError::ZeroValue)
+                }
+            }
+
+            impl Encode for $t {
+                fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError> {
+                    self.get().encode(sink)
+                }
+            }
+
+            impl MeasureFixed for $t {
+                fn measure_fixed() -> usize { 1 }
+            }
+        )+
+    };
+}
+
+impl_non_zero_byte!(NonZeroU8 => u8, NonZeroI8 => i8);
+
+impl Decode<'_> for () {
+    fn decode(_encoded: &[u8], _offset: &mut usize) -> Result<Self, DecodeError> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl DecodeFrom for () {
+    fn decode_from<R: Read + ?Sized>(_reader: &mut R) -> Result<Self, DecodeError> {
+        Ok(())
+    }
+}
+
+impl Encode for () {
+    fn encode<S: Sink + ?Sized>(&self, _sink: &mut S) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+impl MeasureFixed for () {
+    fn measure_fixed() -> usize { 0 }
+}
+
+// A `char` encodes itself as UTF-8; `prim::Char` stores the fixed 4-byte scalar value instead.
+fn utf8_len(first: u8) -> Result<usize, DecodeError> {
+    match first {
+        0x00..=0x7f => Ok(1),
+        0xc0..=0xdf => Ok(2),
+        0xe0..=0xef => Ok(3),
+        0xf0..=0xf7 => Ok(4),
+        _ => Err(DecodeError::InvalidData),
+    }
+}
+
+fn utf8_char(bytes: &[u8]) -> Result<char, DecodeError> {
+    let decoded = core::str::from_utf8(bytes).map_err(|_| DecodeError::InvalidData)?;
+    decoded.chars().next().ok_or(DecodeError::InvalidData)
+}
+
+impl Decode<'_> for char {
+    fn decode(encoded: &[u8], offset: &mut usize) -> Result<Self, DecodeError> {
+        let start = *offset;
+        let [first] = <[u8; 1]>::decode(encoded, offset)?;
+        take_bytes(encoded, offset, utf8_len(first)? - 1)?;
+        utf8_char(&encoded[start..*offset])
+    }
+}
+
+#[cfg(feature = "std")]
+impl DecodeFrom for char {
+    fn decode_from<R: Read + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        let mut bytes = [0u8; 4];
+        reader.read_exact(&mut bytes[..1])?;
+        let len = utf8_len(bytes[0])?;
+        reader.read_exact(&mut bytes[1..len])?;
+        utf8_char(&bytes[..len])
+    }
+}
+
+impl Encode for char {
+    fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError> {
+        let mut bytes = [0u8; 4];
+        sink.write(self.encode_utf8(&mut bytes).as_bytes())
+    }
 }
 
+impl<'encoded, T: Decode<'encoded>> Decode<'encoded> for Wrapping<T> {
+    fn decode(encoded: &'encoded [u8], offset: &mut usize) -> Result<Self, DecodeError> {
+        Ok(Wrapping(T::decode(encoded, offset)?))
+    }
+
+    fn decode_with(encoded: &'encoded [u8], offset: &mut usize, budget: &mut Budget) -> Result<Self, DecodeError> {
+        Ok(Wrapping(T::decode_with(encoded, offset, budget)?))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: DecodeFrom> DecodeFrom for Wrapping<T> {
+    fn decode_from<R: Read + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        Ok(Wrapping(T::decode_from(reader)?))
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(reader: &mut R, budget: &mut Budget) -> Result<Self, DecodeError> {
+        Ok(Wrapping(T::decode_from_with(reader, budget)?))
+    }
+}
+
+impl<T: Encode> Encode for Wrapping<T> {
+    fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError> {
+        self.0.encode(sink)
+    }
+}
+
+impl<T: MeasureFixed> MeasureFixed for Wrapping<T> {
+    fn measure_fixed() -> usize { T::measure_fixed() }
+}
+
+#[cfg(feature = "alloc")]
 macro_rules! impl_smart_ptr {
     ($($t:tt),+ $(,)?) => {
         $(
             impl<'encoded, T: Decode<'encoded>> Decode<'encoded> for $t<T> {
                 fn decode(encoded: &'encoded [u8], offset: &mut usize) -> Result<Self, DecodeError> {
-                    let value = T::decode(encoded, offset)?;
+                    Self::decode_with(encoded, offset, &mut Budget::new())
+                }
+
+                fn decode_with(encoded: &'encoded [u8], offset: &mut usize, budget: &mut Budget) -> Result<Self, DecodeError> {
+                    budget.charge(0, size_of::<T>())?;
+                    let value = T::decode_with(encoded, offset, budget)?;
+                    Ok(Self::new(value))
+                }
+            }
+
+            #[cfg(feature = "std")]
+            impl<T: DecodeFrom> DecodeFrom for $t<T> {
+                fn decode_from<R: Read + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+                    Self::decode_from_with(reader, &mut Budget::new())
+                }
+
+                fn decode_from_with<R: Read + ?Sized>(reader: &mut R, budget: &mut Budget) -> Result<Self, DecodeError> {
+                    budget.charge(0, size_of::<T>())?;
+                    let value = T::decode_from_with(reader, budget)?;
                     Ok(Self::new(value))
                 }
             }
 
             impl<T: Encode> Encode for $t<T> {
-                fn encode(&self, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError> {
-                    self.as_ref().encode(encoded, offset)
+                fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError> {
+                    self.as_ref().encode(sink)
                 }
             }
 
@@ -323,17 +661,12 @@ macro_rules! impl_smart_ptr {
                     T::measure_fixed()
                 }
             }
-
-            impl<T: Measure> Measure for $t<T> {
-                fn measure(&self) -> Result<usize, EncodeError> {
-                    self.as_ref().measure()
-                }
-            }
         )*
     };
 }
 
 // Note: Rc and Arc are not implemented as they brings special ownership semantics that may not be desired in all contexts.
+#[cfg(feature = "alloc")]
 impl_smart_ptr!(Box);
 
 // conventional implementations
@@ -344,33 +677,36 @@ impl<'encoded> Decode<'encoded> for &'encoded CStr {
 }
 
 impl Encode for &'_ CStr {
-    fn encode(&self, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError> {
-        crate::var::str::CStr::codec().encode(self, encoded, offset)
+    fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError> {
+        crate::var::str::CStr::codec().encode(self, sink)
     }
 }
 
-impl Measure for &'_ CStr {
-    fn measure(&self) -> Result<usize, EncodeError> {
-        crate::var::str::CStr::codec().measure(self)
-    }
-}
-
-impl DecodeOwned for CString {}
-
+#[cfg(feature = "alloc")]
 impl Decode<'_> for CString {
     fn decode(encoded: &[u8], offset: &mut usize) -> Result<Self, DecodeError> {
         crate::var::str::CString::codec().decode(encoded, offset)
     }
+
+    fn decode_with(encoded: &[u8], offset: &mut usize, budget: &mut Budget) -> Result<Self, DecodeError> {
+        crate::var::str::CString::codec().decode_with(encoded, offset, budget)
+    }
 }
 
-impl Encode for CString {
-    fn encode(&self, encoded: &mut [u8], offset: &mut usize) -> Result<(), EncodeError> {
-        crate::var::str::CString::codec().encode(self, encoded, offset)
+#[cfg(feature = "std")]
+impl DecodeFrom for CString {
+    fn decode_from<R: Read + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        crate::var::str::CString::codec().decode_from(reader)
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(reader: &mut R, budget: &mut Budget) -> Result<Self, DecodeError> {
+        crate::var::str::CString::codec().decode_from_with(reader, budget)
     }
 }
 
-impl Measure for CString {
-    fn measure(&self) -> Result<usize, EncodeError> {
-        crate::var::str::CString::codec().measure(self)
+#[cfg(feature = "alloc")]
+impl Encode for CString {
+    fn encode<S: Sink + ?Sized>(&self, sink: &mut S) -> Result<(), EncodeError> {
+        crate::var::str::CString::codec().encode(self, sink)
     }
 }
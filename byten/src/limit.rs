@@ -0,0 +1,172 @@
+#[cfg(feature = "std")]
+use core::{any::TypeId, cell::RefCell};
+
+use crate::DecodeError;
+
+// Caps what a single decode may allocate, counted across every nested codec. Passed to
+// `decode_with`/`decode_from_with`, which spend it down as they go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    pub max_bytes: usize,
+    pub max_items: usize,
+}
+
+impl Budget {
+    pub const UNLIMITED: Budget = Budget {
+        max_bytes: usize::MAX,
+        max_items: usize::MAX,
+    };
+
+    pub const fn new() -> Self {
+        Self::UNLIMITED
+    }
+
+    pub const fn with_max_bytes(self, max_bytes: usize) -> Self {
+        Budget { max_bytes, ..self }
+    }
+
+    pub const fn with_max_items(self, max_items: usize) -> Self {
+        Budget { max_items, ..self }
+    }
+
+    // Accounts for an allocation of `items` items taking `bytes` bytes.
+    pub fn charge(&mut self, items: usize, bytes: usize) -> Result<(), DecodeError> {
+        let max_items = self.max_items.checked_sub(items).ok_or(DecodeError::BudgetExceeded)?;
+        let max_bytes = self.max_bytes.checked_sub(bytes).ok_or(DecodeError::BudgetExceeded)?;
+        *self = Budget { max_bytes, max_items };
+        Ok(())
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self { Self::new() }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    // Nesting per guarded type, so that one type's limit does not count the nesting of others.
+    static DEPTHS: RefCell<Vec<(TypeId, usize)>> = const { RefCell::new(Vec::new()) };
+}
+
+// Holds one level of nesting of a guarded type on this thread until dropped.
+#[cfg(feature = "std")]
+pub struct DepthGuard {
+    key: TypeId,
+}
+
+// Fails once more than `max_depth` decodes of `T` are nested on this thread. Depth lives in
+// thread-local state, so this, `SelfCodec::with_max_depth` and `#[byten(max_depth)]` need `std`.
+#[cfg(feature = "std")]
+pub fn enter<T: ?Sized + 'static>(max_depth: usize) -> Result<DepthGuard, DecodeError> {
+    enter_key(TypeId::of::<T>(), max_depth)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn enter_key(key: TypeId, max_depth: usize) -> Result<DepthGuard, DecodeError> {
+    DEPTHS.with_borrow_mut(|depths| {
+        match depths.iter_mut().find(|(entered, _)| *entered == key) {
+            Some((_, depth)) if *depth >= max_depth => return Err(DecodeError::DepthLimitExceeded),
+            Some((_, depth)) => *depth += 1,
+            None if max_depth == 0 => return Err(DecodeError::DepthLimitExceeded),
+            None => depths.push((key, 1)),
+        }
+        Ok(DepthGuard { key })
+    })
+}
+
+#[cfg(feature = "std")]
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        DEPTHS.with_borrow_mut(|depths| {
+            let index = depths.iter().position(|(entered, _)| *entered == self.key).expect("Guarded type was entered");
+            depths[index].1 -= 1;
+            if depths[index].1 == 0 {
+                depths.swap_remove(index);
+            }
+        });
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use crate::{DecodeErrorKind, Decoder as _, ReadDecoder as _, SelfCodec, fix, prim, util, var};
+
+    use super::*;
+
+    #[test]
+    fn test_max_length() {
+        let encoded = [3, 0, 1, 0, 2, 0, 3];
+
+        let vec = var::Vec::<var::USizeBE, prim::U16BE>::default().with_max_length(2);
+        let error = vec.decode(&encoded, &mut 0).expect_err("Decoding past the max length succeeded");
+        assert!(matches!(error, DecodeError::LengthLimitExceeded));
+        let error = vec.decode_from(&mut encoded.as_slice()).expect_err("Reading past the max length succeeded");
+        assert!(matches!(error, DecodeError::LengthLimitExceeded));
+
+        let vec = vec.with_max_length(3);
+        assert_eq!(vec.decode(&encoded, &mut 0).expect("Decoding failed"), [1, 2, 3]);
+
+        let string = var::str::String::<var::USizeBE>::default().with_max_length(1);
+        let error = string.decode(b"\x02hi", &mut 0).expect_err("Decoding past the max length succeeded");
+        assert!(matches!(error, DecodeError::LengthLimitExceeded));
+        let error = string.decode_from(&mut &b"\x02hi"[..]).expect_err("Reading past the max length succeeded");
+        assert!(matches!(error, DecodeError::LengthLimitExceeded));
+    }
+
+    #[test]
+    fn test_cstr_max_length() {
+        let cstr = var::str::CStr::codec().with_max_length(2);
+        assert_eq!(cstr.decode(b"hi\0", &mut 0).expect("Decoding failed").to_bytes(), b"hi");
+        let error = cstr.decode(b"hey\0", &mut 0).expect_err("Decoding past the max length succeeded");
+        assert!(matches!(error, DecodeError::LengthLimitExceeded));
+        let error = cstr.decode(b"hi", &mut 0).expect_err("Decoding an unterminated string succeeded");
+        assert!(matches!(error, DecodeError::Incomplete { needed: None }));
+
+        // A reader that never sends the terminator is cut off rather than buffered forever.
+        let cstring = var::str::CString::codec().with_max_length(1024);
+        let mut endless = std::io::repeat(b'x');
+        let error = cstring.decode_from(&mut endless).expect_err("Reading past the max length succeeded");
+        assert!(matches!(error, DecodeError::LengthLimitExceeded));
+    }
+
+    #[test]
+    fn test_budget() {
+        // The length claims far more items than the input holds.
+        let encoded = [0x8f, 0xff, 0xff, 0xff, 0x7f, 0, 1];
+        let vec = var::Vec::<var::USizeBE, SelfCodec<u8>>::default();
+
+        let error = vec.decode_with(&encoded, &mut 0, &mut Budget::new().with_max_items(1024))
+            .expect_err("Decoding past the item budget succeeded");
+        assert!(matches!(error.kind(), DecodeErrorKind::BudgetExceeded));
+
+        let error = vec.decode_from_with(&mut encoded.as_slice(), &mut Budget::new().with_max_bytes(1024))
+            .expect_err("Reading past the byte budget succeeded");
+        assert!(matches!(error.kind(), DecodeErrorKind::BudgetExceeded));
+
+        let strings = var::Vec::<var::USizeBE, var::str::String<var::USizeBE>>::default();
+        let encoded = b"\x02\x03abc\x03def";
+        let mut budget = Budget::new().with_max_bytes(2 * size_of::<String>() + 5);
+        let error = strings.decode_with(encoded, &mut 0, &mut budget)
+            .expect_err("Decoding past the byte budget succeeded");
+        assert!(matches!(error.kind(), DecodeErrorKind::BudgetExceeded));
+
+        let mut budget = Budget::new().with_max_bytes(2 * size_of::<String>() + 6);
+        let decoded = strings.decode_with(encoded, &mut 0, &mut budget).expect("Decoding within the budget failed");
+        assert_eq!(decoded, ["abc", "def"]);
+        assert_eq!(budget, Budget::new().with_max_bytes(0).with_max_items(usize::MAX - 2));
+
+        // Nested codecs spend the same budget.
+        let boxed = SelfCodec::<Box<[Box<u8>; 2]>>::default();
+        let mut budget = Budget::new().with_max_bytes(size_of::<[Box<u8>; 2]>() + 1);
+        let error = boxed.decode_with(&[0; 2], &mut 0, &mut budget).expect_err("Decoding past the byte budget succeeded");
+        assert!(matches!(error.kind(), DecodeErrorKind::BudgetExceeded));
+
+        let bytes = var::Vec::<var::USizeBE, SelfCodec<u8>>::default();
+        let error = fix::Const::codec(&bytes, vec![1, 2]).decode_with(&[2, 1, 2], &mut 0, &mut Budget::new().with_max_items(1))
+            .expect_err("Decoding past the item budget succeeded");
+        assert!(matches!(error.kind(), DecodeErrorKind::BudgetExceeded));
+        let error = util::Wrapping::codec(&bytes).decode_from_with(&mut [2, 1, 2].as_slice(), &mut Budget::new().with_max_items(1))
+            .expect_err("Reading past the item budget succeeded");
+        assert!(matches!(error.kind(), DecodeErrorKind::BudgetExceeded));
+    }
+}
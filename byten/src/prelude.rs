@@ -1,31 +1,35 @@
-use crate::{Encode, EncodeError, Encoder, Measure, Measurer};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
+#[cfg(feature = "alloc")]
+use crate::{Encode, EncodeError, Encoder};
+
+#[cfg(feature = "alloc")]
 pub trait EncodeToVec {
   fn encode_to_vec(&self) -> Result<Vec<u8>, EncodeError>;
 }
 
-impl<T: Encode + Measure> EncodeToVec for T {
+#[cfg(feature = "alloc")]
+impl<T: Encode + ?Sized> EncodeToVec for T {
     fn encode_to_vec(&self) -> Result<Vec<u8>, EncodeError> {
-        let size = self.measure()?;
-        let mut vec = vec![0u8; size];
-        let mut offset = 0;
-        self.encode(&mut vec, &mut offset)?;
+        let mut vec = Vec::new();
+        self.encode(&mut vec)?;
         Ok(vec)
     }
 }
 
+#[cfg(feature = "alloc")]
 pub trait EncoderToVec {
-    type Decoded;
+    type Decoded: ?Sized;
     fn encode_to_vec(&self, decoded: &Self::Decoded) -> Result<Vec<u8>, EncodeError>;
 }
 
-impl<Decoded, C: Encoder<Decoded=Decoded> + Measurer<Decoded=Decoded>> EncoderToVec for C {
-    type Decoded = Decoded;
+#[cfg(feature = "alloc")]
+impl<C: Encoder + ?Sized> EncoderToVec for C {
+    type Decoded = C::Decoded;
     fn encode_to_vec(&self, decoded: &Self::Decoded) -> Result<Vec<u8>, EncodeError> {
-        let size = self.measure(decoded)?;
-        let mut vec = vec![0u8; size];
-        let mut offset = 0;
-        self.encode(decoded, &mut vec, &mut offset)?;
+        let mut vec = Vec::new();
+        self.encode(decoded, &mut vec)?;
         Ok(vec)
     }
 }
@@ -1,75 +1,490 @@
+use core::marker::PhantomData;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    Big,
+    Little,
+}
+
+impl ByteOrder {
+    pub const NATIVE: ByteOrder = if cfg!(target_endian = "big") { ByteOrder::Big } else { ByteOrder::Little };
+}
+
+pub trait Endian {
+    const ORDER: ByteOrder;
+}
+
+pub struct BigEndian;
+pub struct LittleEndian;
+pub struct NativeEndian;
+
+impl Endian for BigEndian {
+    const ORDER: ByteOrder = ByteOrder::Big;
+}
+
+impl Endian for LittleEndian {
+    const ORDER: ByteOrder = ByteOrder::Little;
+}
+
+impl Endian for NativeEndian {
+    const ORDER: ByteOrder = ByteOrder::NATIVE;
+}
+
+// `$be`/`$le` are both a type alias and a constant, so they keep working as `#[byten(U32BE)]` and in type position.
+// The static codecs delegate to `$dyn`, whose byte order is only known at runtime.
 macro_rules! impl_prim {
-    ($ty:tt, $codec:ident, $from_bytes:ident, $to_bytes:ident) => {
-        pub struct $codec;
+    ($ty:tt, $codec:ident, $dyn:ident, $be:ident, $le:ident) => {
+        pub struct $codec<E>(PhantomData<E>);
 
-        impl $codec {
+        pub type $be = $codec<BigEndian>;
+        pub const $be: $be = $codec::codec();
+
+        pub type $le = $codec<LittleEndian>;
+        pub const $le: $le = $codec::codec();
+
+        impl<E> $codec<E> {
             pub const fn codec() -> Self {
-                $codec
+                $codec(PhantomData)
             }
         }
 
-        impl Default for $codec {
+        impl<E> Default for $codec<E> {
             fn default() -> Self { Self::codec() }
         }
 
-        impl<'encoded> crate::Decoder<'encoded, '_> for $codec {
+        impl_prim!(@delegate $ty, $codec, $dyn);
+
+        pub struct $dyn {
+            pub order: ByteOrder,
+        }
+
+        impl $dyn {
+            pub const fn codec(order: ByteOrder) -> Self {
+                Self { order }
+            }
+
+            fn read_bytes(&self, bytes: [u8; size_of::<$ty>()]) -> $ty {
+                match self.order {
+                    ByteOrder::Big => <$ty>::from_be_bytes(bytes),
+                    ByteOrder::Little => <$ty>::from_le_bytes(bytes),
+                }
+            }
+        }
+
+        impl<'encoded> crate::Decoder<'encoded, '_> for $dyn {
             type Decoded = $ty;
             fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
-                const SIZE: usize = $ty::BITS as usize / 8;
-                if *offset + SIZE > encoded.len() {
-                    return Err(crate::DecodeError::EOF);
-                }
-                let bytes: [u8; SIZE] = encoded[*offset..*offset + SIZE].try_into().unwrap();
-                *offset += SIZE;
-                Ok(<$ty>::$from_bytes(bytes))
+                let bytes = <[u8; size_of::<$ty>()] as crate::Decode>::decode(encoded, offset)?;
+                Ok(self.read_bytes(bytes))
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl crate::ReadDecoder for $dyn {
+            type Decoded = $ty;
+            fn decode_from<R: std::io::Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+                let mut bytes = [0u8; size_of::<$ty>()];
+                reader.read_exact(&mut bytes)?;
+                Ok(self.read_bytes(bytes))
             }
         }
 
-        impl crate::Encoder for $codec {
+        impl crate::Encoder for $dyn {
             type Decoded = $ty;
-            fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
-                const SIZE: usize = $ty::BITS as usize / 8;
-                if *offset + SIZE > encoded.len() {
-                    return Err(crate::EncodeError::BufferTooSmall);
+            fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+                match self.order {
+                    ByteOrder::Big => sink.write(&decoded.to_be_bytes()),
+                    ByteOrder::Little => sink.write(&decoded.to_le_bytes()),
                 }
-                let bytes = decoded.$to_bytes();
-                encoded[*offset..*offset + SIZE].copy_from_slice(&bytes);
-                *offset += SIZE;
-                Ok(())
             }
         }
 
-        impl crate::FixedMeasurer for $codec {
+        impl crate::FixedMeasurer for $dyn {
             fn measure_fixed(&self) -> usize {
-                $ty::BITS as usize / 8
+                size_of::<$ty>()
+            }
+        }
+    };
+    (@delegate $ty:tt, $codec:ident, $dyn:ident) => {
+        impl<'encoded, E: Endian> crate::Decoder<'encoded, '_> for $codec<E> {
+            type Decoded = $ty;
+            fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
+                $dyn::codec(E::ORDER).decode(encoded, offset)
             }
         }
 
-        impl crate::Measurer for $codec {
+        #[cfg(feature = "std")]
+        impl<E: Endian> crate::ReadDecoder for $codec<E> {
             type Decoded = $ty;
-            fn measure(&self, _decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
-                Ok(crate::FixedMeasurer::measure_fixed(self))
+            fn decode_from<R: std::io::Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+                $dyn::codec(E::ORDER).decode_from(reader)
+            }
+        }
+
+        impl<E: Endian> crate::Encoder for $codec<E> {
+            type Decoded = $ty;
+            fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+                $dyn::codec(E::ORDER).encode(decoded, sink)
+            }
+        }
+
+        impl<E: Endian> crate::FixedMeasurer for $codec<E> {
+            fn measure_fixed(&self) -> usize {
+                $dyn::codec(E::ORDER).measure_fixed()
             }
         }
     };
 }
 
-// BE
-impl_prim!(u16, U16BE, from_be_bytes, to_be_bytes);
-impl_prim!(u32, U32BE, from_be_bytes, to_be_bytes);
-impl_prim!(u64, U64BE, from_be_bytes, to_be_bytes);
-impl_prim!(u128, U128BE, from_be_bytes, to_be_bytes);
-impl_prim!(i16, I16BE, from_be_bytes, to_be_bytes);
-impl_prim!(i32, I32BE, from_be_bytes, to_be_bytes);
-impl_prim!(i64, I64BE, from_be_bytes, to_be_bytes);
-impl_prim!(i128, I128BE, from_be_bytes, to_be_bytes);
-
-// LE
-impl_prim!(u16, U16LE, from_le_bytes, to_le_bytes);
-impl_prim!(u32, U32LE, from_le_bytes, to_le_bytes);
-impl_prim!(u64, U64LE, from_le_bytes, to_le_bytes);
-impl_prim!(u128, U128LE, from_le_bytes, to_le_bytes);
-impl_prim!(i16, I16LE, from_le_bytes, to_le_bytes);
-impl_prim!(i32, I32LE, from_le_bytes, to_le_bytes);
-impl_prim!(i64, I64LE, from_le_bytes, to_le_bytes);
-impl_prim!(i128, I128LE, from_le_bytes, to_le_bytes);
+impl_prim!(u16, U16, U16Dyn, U16BE, U16LE);
+impl_prim!(u32, U32, U32Dyn, U32BE, U32LE);
+impl_prim!(u64, U64, U64Dyn, U64BE, U64LE);
+impl_prim!(u128, U128, U128Dyn, U128BE, U128LE);
+impl_prim!(i16, I16, I16Dyn, I16BE, I16LE);
+impl_prim!(i32, I32, I32Dyn, I32BE, I32LE);
+impl_prim!(i64, I64, I64Dyn, I64BE, I64LE);
+impl_prim!(i128, I128, I128Dyn, I128BE, I128LE);
+impl_prim!(f32, F32, F32Dyn, F32BE, F32LE);
+impl_prim!(f64, F64, F64Dyn, F64BE, F64LE);
+
+// Integers narrower than their Rust type, stored in the low `$width` bytes and sign-extended on decode.
+macro_rules! impl_odd_prim {
+    ($ty:tt, $codec:ident, $dyn:ident, $width:literal, $be:ident, $le:ident) => {
+        pub struct $codec<E>(PhantomData<E>);
+
+        pub type $be = $codec<BigEndian>;
+        pub const $be: $be = $codec::codec();
+
+        pub type $le = $codec<LittleEndian>;
+        pub const $le: $le = $codec::codec();
+
+        impl<E> $codec<E> {
+            pub const fn codec() -> Self {
+                $codec(PhantomData)
+            }
+        }
+
+        impl<E> Default for $codec<E> {
+            fn default() -> Self { Self::codec() }
+        }
+
+        impl_prim!(@delegate $ty, $codec, $dyn);
+
+        pub struct $dyn {
+            pub order: ByteOrder,
+        }
+
+        impl $dyn {
+            const SHIFT: u32 = <$ty>::BITS - $width * 8;
+
+            pub const fn codec(order: ByteOrder) -> Self {
+                Self { order }
+            }
+
+            fn start(&self) -> usize {
+                match self.order {
+                    ByteOrder::Big => size_of::<$ty>() - $width,
+                    ByteOrder::Little => 0,
+                }
+            }
+
+            fn read_bytes(&self, bytes: [u8; $width]) -> $ty {
+                let mut buffer = [0u8; size_of::<$ty>()];
+                buffer[self.start()..self.start() + $width].copy_from_slice(&bytes);
+                let value = match self.order {
+                    ByteOrder::Big => <$ty>::from_be_bytes(buffer),
+                    ByteOrder::Little => <$ty>::from_le_bytes(buffer),
+                };
+                (value << Self::SHIFT) >> Self::SHIFT
+            }
+        }
+
+        impl<'encoded> crate::Decoder<'encoded, '_> for $dyn {
+            type Decoded = $ty;
+            fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
+                let bytes = <[u8; $width] as crate::Decode>::decode(encoded, offset)?;
+                Ok(self.read_bytes(bytes))
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl crate::ReadDecoder for $dyn {
+            type Decoded = $ty;
+            fn decode_from<R: std::io::Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+                let mut bytes = [0u8; $width];
+                reader.read_exact(&mut bytes)?;
+                Ok(self.read_bytes(bytes))
+            }
+        }
+
+        impl crate::Encoder for $dyn {
+            type Decoded = $ty;
+            fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+                if (*decoded << Self::SHIFT) >> Self::SHIFT != *decoded {
+                    return Err(crate::EncodeError::OutOfRange);
+                }
+                let bytes = match self.order {
+                    ByteOrder::Big => decoded.to_be_bytes(),
+                    ByteOrder::Little => decoded.to_le_bytes(),
+                };
+                sink.write(&bytes[self.start()..self.start() + $width])
+            }
+        }
+
+        impl crate::FixedMeasurer for $dyn {
+            fn measure_fixed(&self) -> usize {
+                $width
+            }
+        }
+    };
+}
+
+impl_odd_prim!(u32, U24, U24Dyn, 3, U24BE, U24LE);
+impl_odd_prim!(u64, U40, U40Dyn, 5, U40BE, U40LE);
+impl_odd_prim!(u64, U48, U48Dyn, 6, U48BE, U48LE);
+impl_odd_prim!(u64, U56, U56Dyn, 7, U56BE, U56LE);
+impl_odd_prim!(i32, I24, I24Dyn, 3, I24BE, I24LE);
+impl_odd_prim!(i64, I40, I40Dyn, 5, I40BE, I40LE);
+impl_odd_prim!(i64, I48, I48Dyn, 6, I48BE, I48LE);
+impl_odd_prim!(i64, I56, I56Dyn, 7, I56BE, I56LE);
+
+// A `char` stored as its 4-byte Unicode scalar value.
+pub struct Char<E>(PhantomData<E>);
+
+pub type CharBE = Char<BigEndian>;
+#[allow(non_upper_case_globals)]
+pub const CharBE: CharBE = Char::codec();
+
+pub type CharLE = Char<LittleEndian>;
+#[allow(non_upper_case_globals)]
+pub const CharLE: CharLE = Char::codec();
+
+impl<E> Char<E> {
+    pub const fn codec() -> Self {
+        Char(PhantomData)
+    }
+}
+
+impl<E> Default for Char<E> {
+    fn default() -> Self { Self::codec() }
+}
+
+impl<'encoded, E: Endian> crate::Decoder<'encoded, '_> for Char<E> {
+    type Decoded = char;
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
+        let scalar = U32Dyn::codec(E::ORDER).decode(encoded, offset)?;
+        char::from_u32(scalar).ok_or(crate::DecodeError::InvalidData)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Endian> crate::ReadDecoder for Char<E> {
+    type Decoded = char;
+    fn decode_from<R: std::io::Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        let scalar = U32Dyn::codec(E::ORDER).decode_from(reader)?;
+        char::from_u32(scalar).ok_or(crate::DecodeError::InvalidData)
+    }
+}
+
+impl<E: Endian> crate::Encoder for Char<E> {
+    type Decoded = char;
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+        U32Dyn::codec(E::ORDER).encode(&(*decoded as u32), sink)
+    }
+}
+
+impl<E: Endian> crate::FixedMeasurer for Char<E> {
+    fn measure_fixed(&self) -> usize {
+        4
+    }
+}
+
+pub trait Float: Copy {
+    const CANONICAL_NAN: Self;
+    fn is_nan(self) -> bool;
+}
+
+impl Float for f32 {
+    const CANONICAL_NAN: Self = f32::from_bits(0x7fc0_0000);
+    fn is_nan(self) -> bool { f32::is_nan(self) }
+}
+
+impl Float for f64 {
+    const CANONICAL_NAN: Self = f64::from_bits(0x7ff8_0000_0000_0000);
+    fn is_nan(self) -> bool { f64::is_nan(self) }
+}
+
+// Collapses every NaN payload into a single quiet NaN so equal records always encode to equal bytes.
+pub struct CanonicalNaN<Codec>(pub Codec);
+
+impl<Codec> CanonicalNaN<Codec> {
+    pub const fn codec(codec: Codec) -> Self {
+        Self(codec)
+    }
+
+    fn canonicalize<F: Float>(value: F) -> F {
+        if value.is_nan() { F::CANONICAL_NAN } else { value }
+    }
+}
+
+impl<Codec> Default for CanonicalNaN<Codec>
+where
+    Codec: Default,
+{
+    fn default() -> Self { Self::codec(Codec::default()) }
+}
+
+impl<'encoded, 'decoded, Codec> crate::Decoder<'encoded, 'decoded> for CanonicalNaN<Codec>
+where
+    Codec: crate::Decoder<'encoded, 'decoded>,
+    Codec::Decoded: Float,
+{
+    type Decoded = Codec::Decoded;
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
+        self.0.decode(encoded, offset).map(Self::canonicalize)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Codec> crate::ReadDecoder for CanonicalNaN<Codec>
+where
+    Codec: crate::ReadDecoder,
+    Codec::Decoded: Float,
+{
+    type Decoded = Codec::Decoded;
+    fn decode_from<R: std::io::Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.0.decode_from(reader).map(Self::canonicalize)
+    }
+}
+
+impl<Codec> crate::Encoder for CanonicalNaN<Codec>
+where
+    Codec: crate::Encoder,
+    Codec::Decoded: Float,
+{
+    type Decoded = Codec::Decoded;
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+        self.0.encode(&Self::canonicalize(*decoded), sink)
+    }
+}
+
+impl<Codec> crate::FixedMeasurer for CanonicalNaN<Codec>
+where
+    Codec: crate::FixedMeasurer,
+    Codec::Decoded: Float,
+{
+    fn measure_fixed(&self) -> usize {
+        self.0.measure_fixed()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use crate::prelude::EncoderToVec;
+    use crate::{Decoder as _, FixedMeasurer as _, ReadDecoder as _};
+
+    use super::*;
+
+    #[test]
+    fn test_floats() {
+        assert_eq!(F32BE.encode_to_vec(&1.5).expect("Encoding failed"), [0x3f, 0xc0, 0x00, 0x00]);
+        assert_eq!(F32LE.encode_to_vec(&1.5).expect("Encoding failed"), [0x00, 0x00, 0xc0, 0x3f]);
+        assert_eq!(F64BE.encode_to_vec(&-2.0).expect("Encoding failed"), [0xc0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(F64LE.encode_to_vec(&-2.0).expect("Encoding failed"), [0, 0, 0, 0, 0, 0, 0, 0xc0]);
+        assert_eq!(F32BE.measure_fixed(), 4);
+        assert_eq!(F64LE.measure_fixed(), 8);
+
+        for value in [0.0, -0.0, 1.0e-300, f64::MAX, f64::INFINITY, f64::NEG_INFINITY] {
+            let encoded = F64BE.encode_to_vec(&value).expect("Encoding failed");
+            let decoded = F64BE.decode(&encoded, &mut 0).expect("Decoding failed");
+            assert_eq!(decoded.to_bits(), value.to_bits());
+            let decoded = F64BE.decode_from(&mut encoded.as_slice()).expect("Reading failed");
+            assert_eq!(decoded.to_bits(), value.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_generic_endian() {
+        assert_eq!(U32::<BigEndian>::codec().encode_to_vec(&0x01020304).expect("Encoding failed"), [1, 2, 3, 4]);
+        assert_eq!(U32::<LittleEndian>::codec().encode_to_vec(&0x01020304).expect("Encoding failed"), [4, 3, 2, 1]);
+        assert_eq!(U32::<NativeEndian>::codec().encode_to_vec(&0x01020304).expect("Encoding failed"), 0x01020304u32.to_ne_bytes());
+        assert_eq!(I16::<NativeEndian>::codec().decode(&(-3i16).to_ne_bytes(), &mut 0).expect("Decoding failed"), -3);
+        assert_eq!(U24::<NativeEndian>::codec().measure_fixed(), 3);
+    }
+
+    #[test]
+    fn test_runtime_byte_order() {
+        assert_eq!(U32Dyn::codec(ByteOrder::Big).encode_to_vec(&0x01020304).expect("Encoding failed"), [1, 2, 3, 4]);
+        assert_eq!(U32Dyn::codec(ByteOrder::Little).encode_to_vec(&0x01020304).expect("Encoding failed"), [4, 3, 2, 1]);
+        assert_eq!(I24Dyn::codec(ByteOrder::Little).encode_to_vec(&-2).expect("Encoding failed"), [0xfe, 0xff, 0xff]);
+        assert_eq!(U48Dyn::codec(ByteOrder::Big).measure_fixed(), 6);
+        assert!(U24Dyn::codec(ByteOrder::Big).encode_to_vec(&0x01000000).is_err());
+
+        for order in [ByteOrder::Big, ByteOrder::Little] {
+            let codec = I40Dyn::codec(order);
+            let encoded = codec.encode_to_vec(&-0x0102030405).expect("Encoding failed");
+            assert_eq!(codec.decode(&encoded, &mut 0).expect("Decoding failed"), -0x0102030405);
+            assert_eq!(codec.decode_from(&mut encoded.as_slice()).expect("Reading failed"), -0x0102030405);
+
+            let codec = F64Dyn::codec(order);
+            let encoded = codec.encode_to_vec(&-2.5).expect("Encoding failed");
+            assert_eq!(codec.decode(&encoded, &mut 0).expect("Decoding failed"), -2.5);
+        }
+        assert_eq!(U16Dyn::codec(ByteOrder::NATIVE).decode(&7u16.to_ne_bytes(), &mut 0).expect("Decoding failed"), 7);
+    }
+
+    #[test]
+    fn test_char() {
+        assert_eq!(CharBE.encode_to_vec(&'é').expect("Encoding failed"), [0, 0, 0, 0xe9]);
+        assert_eq!(CharLE.encode_to_vec(&'🦀').expect("Encoding failed"), [0x80, 0xf9, 0x01, 0]);
+        assert_eq!(CharLE.decode(&[0x80, 0xf9, 0x01, 0], &mut 0).expect("Decoding failed"), '🦀');
+        assert_eq!(CharBE.decode_from(&mut [0, 0, 0, 0x41].as_slice()).expect("Reading failed"), 'A');
+        assert_eq!(CharBE.measure_fixed(), 4);
+        assert!(CharBE.decode(&[0, 0, 0xd8, 0], &mut 0).is_err());
+        assert!(CharBE.decode(&[0, 0x11, 0, 0], &mut 0).is_err());
+    }
+
+    #[test]
+    fn test_odd_widths() {
+        assert_eq!(U24BE.encode_to_vec(&0x123456).expect("Encoding failed"), [0x12, 0x34, 0x56]);
+        assert_eq!(U24LE.encode_to_vec(&0x123456).expect("Encoding failed"), [0x56, 0x34, 0x12]);
+        assert_eq!(U48BE.encode_to_vec(&0x0102_0304_0506).expect("Encoding failed"), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(I24BE.encode_to_vec(&-2).expect("Encoding failed"), [0xff, 0xff, 0xfe]);
+        assert_eq!(I40LE.encode_to_vec(&-2).expect("Encoding failed"), [0xfe, 0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(U56LE.measure_fixed(), 7);
+
+        assert_eq!(U24BE.decode(&[0xff, 0xff, 0xff], &mut 0).expect("Decoding failed"), 0xff_ffff);
+        assert_eq!(I24BE.decode(&[0xff, 0xff, 0xff], &mut 0).expect("Decoding failed"), -1);
+        assert_eq!(I24LE.decode(&[0x00, 0x00, 0x80], &mut 0).expect("Decoding failed"), -0x80_0000);
+        assert_eq!(I56BE.decode_from(&mut &[0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff][..]).expect("Reading failed"), (1 << 55) - 1);
+
+        for value in [0, 1, -1, (1 << 47) - 1, -(1 << 47)] {
+            let encoded = I48BE.encode_to_vec(&value).expect("Encoding failed");
+            assert_eq!(I48BE.decode(&encoded, &mut 0).expect("Decoding failed"), value);
+            let encoded = I48LE.encode_to_vec(&value).expect("Encoding failed");
+            assert_eq!(I48LE.decode(&encoded, &mut 0).expect("Decoding failed"), value);
+        }
+
+        assert!(matches!(U24BE.encode_to_vec(&0x100_0000), Err(crate::EncodeError::OutOfRange)));
+        assert!(matches!(U40LE.encode_to_vec(&(1 << 40)), Err(crate::EncodeError::OutOfRange)));
+        assert!(matches!(I24BE.encode_to_vec(&0x80_0000), Err(crate::EncodeError::OutOfRange)));
+        assert!(matches!(I56LE.encode_to_vec(&(-(1 << 55) - 1)), Err(crate::EncodeError::OutOfRange)));
+    }
+
+    #[test]
+    fn test_canonical_nan() {
+        let payload_nan = f32::from_bits(0x7fc0_1234);
+        let negative_nan = f32::from_bits(0xffc0_0000);
+        let codec = CanonicalNaN::<F32BE>::default();
+
+        let canonical = codec.encode_to_vec(&f32::NAN).expect("Encoding failed");
+        assert_eq!(canonical, [0x7f, 0xc0, 0x00, 0x00]);
+        assert_eq!(codec.encode_to_vec(&payload_nan).expect("Encoding failed"), canonical);
+        assert_eq!(codec.encode_to_vec(&negative_nan).expect("Encoding failed"), canonical);
+        assert_eq!(codec.encode_to_vec(&2.5).expect("Encoding failed"), F32BE.encode_to_vec(&2.5).expect("Encoding failed"));
+
+        let encoded = F32BE.encode_to_vec(&payload_nan).expect("Encoding failed");
+        assert_eq!(codec.decode(&encoded, &mut 0).expect("Decoding failed").to_bits(), 0x7fc0_0000);
+        assert_eq!(codec.decode_from(&mut encoded.as_slice()).expect("Reading failed").to_bits(), 0x7fc0_0000);
+    }
+}
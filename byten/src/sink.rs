@@ -0,0 +1,91 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use crate::{EncodeError, Sink};
+
+pub struct CountingSink {
+    count: usize,
+}
+
+impl CountingSink {
+    pub const fn new() -> Self {
+        Self { count: 0 }
+    }
+
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Default for CountingSink {
+    fn default() -> Self { Self::new() }
+}
+
+impl Sink for CountingSink {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.count = self.count.checked_add(bytes.len()).ok_or(EncodeError::InvalidUSize)?;
+        Ok(())
+    }
+}
+
+pub struct SliceSink<'encoded, 'offset> {
+    pub encoded: &'encoded mut [u8],
+    pub offset: &'offset mut usize,
+}
+
+impl<'encoded, 'offset> SliceSink<'encoded, 'offset> {
+    pub fn new(encoded: &'encoded mut [u8], offset: &'offset mut usize) -> Self {
+        Self { encoded, offset }
+    }
+}
+
+impl Sink for SliceSink<'_, '_> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        let end = self.offset.checked_add(bytes.len()).ok_or(EncodeError::BufferTooSmall)?;
+        if end > self.encoded.len() {
+            return Err(EncodeError::BufferTooSmall);
+        }
+        self.encoded[*self.offset..end].copy_from_slice(bytes);
+        *self.offset = end;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Sink for Vec<u8> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tokio-util")]
+impl Sink for bytes::BytesMut {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+pub struct WriteSink<'writer, W: Write + ?Sized> {
+    pub writer: &'writer mut W,
+}
+
+#[cfg(feature = "std")]
+impl<'writer, W: Write + ?Sized> WriteSink<'writer, W> {
+    pub fn new(writer: &'writer mut W) -> Self {
+        Self { writer }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write + ?Sized> Sink for WriteSink<'_, W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.writer.write_all(bytes)?;
+        Ok(())
+    }
+}
@@ -1,5 +1,13 @@
-use core::{borrow, marker::PhantomData};
-use std::ops::Deref;
+use core::{marker::PhantomData, num};
+
+#[cfg(feature = "alloc")]
+use core::{borrow, ops::Deref};
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+
+#[cfg(feature = "std")]
+use std::io::Read;
 
 pub struct Convert<Codec, Decoded> {
     pub codec: Codec,
@@ -31,42 +39,46 @@ where
 {
     type Decoded = Decoded;
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
-        let intermediate = self.codec.decode(encoded, offset)?;
+        self.decode_with(encoded, offset, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let intermediate = self.codec.decode_with(encoded, offset, budget)?;
         let decoded = intermediate.try_into().map_err(Into::into)?;
         Ok(decoded)
     }
 }
 
-impl<Codec, Decoded, Error> crate::Encoder for Convert<Codec, Decoded>
+#[cfg(feature = "std")]
+impl<Codec, Decoded, Error> crate::ReadDecoder for Convert<Codec, Decoded>
 where
-    Codec: crate::Encoder,
-    Decoded: Clone,
-    Codec::Decoded: TryFrom<Decoded, Error = Error>,
-    Error: Into<crate::EncodeError>,
+    Codec: crate::ReadDecoder,
+    Codec::Decoded: TryInto<Decoded, Error = Error>,
+    Error: Into<crate::DecodeError>,
 {
     type Decoded = Decoded;
-    fn encode(
-        &self,
-        decoded: &Self::Decoded,
-        encoded: &mut [u8],
-        offset: &mut usize,
-    ) -> Result<(), crate::EncodeError> {
-        let intermediate = decoded.clone().try_into().map_err(Into::into)?;
-        self.codec.encode(&intermediate, encoded, offset)
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let intermediate = self.codec.decode_from_with(reader, budget)?;
+        let decoded = intermediate.try_into().map_err(Into::into)?;
+        Ok(decoded)
     }
 }
 
-impl<Codec, Decoded, Error> crate::Measurer for Convert<Codec, Decoded>
+impl<Codec, Decoded, Error> crate::Encoder for Convert<Codec, Decoded>
 where
-    Codec: crate::Measurer,
+    Codec: crate::Encoder,
     Decoded: Clone,
     Codec::Decoded: TryFrom<Decoded, Error = Error>,
     Error: Into<crate::EncodeError>,
 {
     type Decoded = Decoded;
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
         let intermediate = decoded.clone().try_into().map_err(Into::into)?;
-        self.codec.measure(&intermediate)
+        self.codec.encode(&intermediate, sink)
     }
 }
 
@@ -82,17 +94,184 @@ where
     }
 }
 
+// Integers with a `core::num::NonZero*` counterpart.
+pub trait NonZeroPrimitive: Sized {
+    type NonZero;
+    fn non_zero(self) -> Option<Self::NonZero>;
+    fn get(non_zero: &Self::NonZero) -> Self;
+}
+
+macro_rules! impl_non_zero_primitive {
+    ($($int:ty => $non_zero:ty),+ $(,)?) => {
+        $(
+            impl NonZeroPrimitive for $int {
+                type NonZero = $non_zero;
+                fn non_zero(self) -> Option<$non_zero> { <$non_zero>::new(self) }
+                fn get(non_zero: &$non_zero) -> Self { non_zero.get() }
+            }
+        )+
+    };
+}
+
+impl_non_zero_primitive!(
+    u8 => num::NonZeroU8, u16 => num::NonZeroU16, u32 => num::NonZeroU32, u64 => num::NonZeroU64,
+    u128 => num::NonZeroU128, usize => num::NonZeroUsize,
+    i8 => num::NonZeroI8, i16 => num::NonZeroI16, i32 => num::NonZeroI32, i64 => num::NonZeroI64,
+    i128 => num::NonZeroI128, isize => num::NonZeroIsize,
+);
+
+// Decodes an integer with `codec` and rejects zero with `DecodeError::ZeroValue`.
+pub struct NonZero<Codec> {
+    pub codec: Codec,
+}
+
+impl<Codec> NonZero<Codec> {
+    pub const fn codec(codec: Codec) -> Self {
+        Self { codec }
+    }
+}
+
+impl<Codec> Default for NonZero<Codec>
+where
+    Codec: Default,
+{
+    fn default() -> Self { Self::codec(Codec::default()) }
+}
+
+impl<'encoded, 'decoded, Codec> crate::Decoder<'encoded, 'decoded> for NonZero<Codec>
+where
+    Codec: crate::Decoder<'encoded, 'decoded>,
+    Codec::Decoded: NonZeroPrimitive,
+    <Codec::Decoded as NonZeroPrimitive>::NonZero: 'decoded,
+{
+    type Decoded = <Codec::Decoded as NonZeroPrimitive>::NonZero;
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_with(encoded, offset, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        self.codec.decode_with(encoded, offset, budget)?.non_zero().ok_or(crate::DecodeError::ZeroValue)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Codec> crate::ReadDecoder for NonZero<Codec>
+where
+    Codec: crate::ReadDecoder,
+    Codec::Decoded: NonZeroPrimitive,
+{
+    type Decoded = <Codec::Decoded as NonZeroPrimitive>::NonZero;
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        self.codec.decode_from_with(reader, budget)?.non_zero().ok_or(crate::DecodeError::ZeroValue)
+    }
+}
+
+impl<Codec> crate::Encoder for NonZero<Codec>
+where
+    Codec: crate::Encoder,
+    Codec::Decoded: NonZeroPrimitive,
+{
+    type Decoded = <Codec::Decoded as NonZeroPrimitive>::NonZero;
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+        self.codec.encode(&NonZeroPrimitive::get(decoded), sink)
+    }
+}
+
+impl<Codec> crate::FixedMeasurer for NonZero<Codec>
+where
+    Codec: crate::FixedMeasurer,
+    Codec::Decoded: NonZeroPrimitive,
+{
+    fn measure_fixed(&self) -> usize {
+        self.codec.measure_fixed()
+    }
+}
+
+pub struct Wrapping<Codec> {
+    pub codec: Codec,
+}
+
+impl<Codec> Wrapping<Codec> {
+    pub const fn codec(codec: Codec) -> Self {
+        Self { codec }
+    }
+}
+
+impl<Codec> Default for Wrapping<Codec>
+where
+    Codec: Default,
+{
+    fn default() -> Self { Self::codec(Codec::default()) }
+}
+
+impl<'encoded, 'decoded, Codec> crate::Decoder<'encoded, 'decoded> for Wrapping<Codec>
+where
+    Codec: crate::Decoder<'encoded, 'decoded>,
+{
+    type Decoded = num::Wrapping<Codec::Decoded>;
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_with(encoded, offset, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        Ok(num::Wrapping(self.codec.decode_with(encoded, offset, budget)?))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Codec> crate::ReadDecoder for Wrapping<Codec>
+where
+    Codec: crate::ReadDecoder,
+{
+    type Decoded = num::Wrapping<Codec::Decoded>;
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        Ok(num::Wrapping(self.codec.decode_from_with(reader, budget)?))
+    }
+}
+
+impl<Codec> crate::Encoder for Wrapping<Codec>
+where
+    Codec: crate::Encoder,
+    Codec::Decoded: Sized,
+{
+    type Decoded = num::Wrapping<Codec::Decoded>;
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+        self.codec.encode(&decoded.0, sink)
+    }
+}
+
+impl<Codec> crate::FixedMeasurer for Wrapping<Codec>
+where
+    Codec: crate::FixedMeasurer,
+    Codec::Decoded: Sized,
+{
+    fn measure_fixed(&self) -> usize {
+        self.codec.measure_fixed()
+    }
+}
+
+#[cfg(feature = "alloc")]
 pub struct Owned<Codec, T> {
     pub codec: Codec,
     pub _marker: PhantomData<T>,
 }
 
+#[cfg(feature = "alloc")]
 impl<Codec, T> Owned<Codec, T> {
     pub const fn codec(codec: Codec) -> Self {
         Self { codec, _marker: PhantomData }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<Codec, T> Default for Owned<Codec, T>
 where
     Codec: Default,
@@ -100,6 +279,7 @@ where
     fn default() -> Self { Self::codec(Codec::default()) }
 }
 
+#[cfg(feature = "alloc")]
 impl<'encoded, 'decoded, Codec, T> crate::Decoder<'encoded, 'decoded> for Owned<Codec, T>
 where
     Codec: crate::Decoder<'encoded, 'decoded>,
@@ -108,38 +288,45 @@ where
 {
     type Decoded = <<Codec::Decoded as Deref>::Target as ToOwned>::Owned;
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
-        let borrowed = self.codec.decode(encoded, offset)?;
+        self.decode_with(encoded, offset, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let borrowed = self.codec.decode_with(encoded, offset, budget)?;
+        budget.charge(0, size_of_val(borrowed.deref()))?;
         Ok(borrowed.deref().to_owned())
     }
 }
 
-impl<Codec, T> crate::Encoder for Owned<Codec, T>
+// Borrowing codecs already decode into their owned form when reading from a stream.
+#[cfg(feature = "std")]
+impl<Codec, T> crate::ReadDecoder for Owned<Codec, T>
 where
-    Codec: crate::Encoder,
-    T: borrow::Borrow<Codec::Decoded>,
+    Codec: crate::ReadDecoder,
 {
-    type Decoded = T;
-    fn encode(
-        &self,
-        decoded: &Self::Decoded,
-        encoded: &mut [u8],
-        offset: &mut usize,
-    ) -> Result<(), crate::EncodeError> {
-        self.codec.encode(decoded.borrow(), encoded, offset)
+    type Decoded = Codec::Decoded;
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.codec.decode_from(reader)
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        self.codec.decode_from_with(reader, budget)
     }
 }
 
-impl<Codec, T> crate::Measurer for Owned<Codec, T>
+#[cfg(feature = "alloc")]
+impl<Codec, T> crate::Encoder for Owned<Codec, T>
 where
-    Codec: crate::Measurer,
+    Codec: crate::Encoder,
     T: borrow::Borrow<Codec::Decoded>,
 {
     type Decoded = T;
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
-        self.codec.measure(decoded.borrow())
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+        self.codec.encode(decoded.borrow(), sink)
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<Codec, T> crate::FixedMeasurer for Owned<Codec, T>
 where
     Codec: crate::FixedMeasurer,
@@ -1,22 +1,42 @@
-use std::{iter, vec::Vec as StdVec, option::Option as StdOption};
+use core::{iter, option::Option as StdOption};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec as StdVec;
+
+#[cfg(feature = "std")]
+use std::io::Read;
 
 use crate::Encode as _;
 use crate::Decode as _;
-use crate::Measure as _;
+
+#[cfg(feature = "std")]
+use crate::DecodeFrom as _;
+
+// Streams give no hint of how much input is left, so a decoded length only reserves up to this many items.
+#[cfg(feature = "std")]
+const MAX_READ_PREALLOCATION: usize = 1024;
 
 pub mod str;
 
+#[cfg(feature = "alloc")]
 pub struct Vec<Length, Item> {
     pub length: Length,
     pub item: Item,
+    pub max_length: usize,
 }
 
+#[cfg(feature = "alloc")]
 impl<Length, Item> Vec<Length, Item> {
     pub const fn codec(length: Length, item: Item) -> Self {
-        Self { length, item }
+        Self { length, item, max_length: usize::MAX }
+    }
+
+    pub fn with_max_length(self, max_length: usize) -> Self {
+        Self { max_length, ..self }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<Length, Item> Default for Vec<Length, Item>
 where
     Length: Default,
@@ -27,6 +47,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'encoded, 'decoded, Length, Item> crate::Decoder<'encoded, 'decoded> for Vec<Length, Item>
 where
     Length: crate::Decoder<'encoded, 'decoded, Decoded = usize>,
@@ -35,50 +56,71 @@ where
     type Decoded = StdVec<Item::Decoded>;
 
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
-        let size = self.length.decode(encoded, offset)?;
-        let mut vec = StdVec::with_capacity(size);
-        for _ in 0..size {
-            let item = self.item.decode(encoded, offset)?;
+        self.decode_with(encoded, offset, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let size = self.length.decode_with(encoded, offset, budget)?;
+        if size > self.max_length {
+            return Err(crate::DecodeError::LengthLimitExceeded);
+        }
+        budget.charge(size, size.saturating_mul(size_of::<Item::Decoded>()))?;
+        // A hostile length must not turn into a huge allocation before any item is decoded.
+        let mut vec = StdVec::with_capacity(size.min(encoded.len().saturating_sub(*offset)));
+        for index in 0..size {
+            let start = *offset;
+            let item = self.item.decode_with(encoded, offset, budget)
+                .map_err(|error| error.at(start).in_index(index))?;
             vec.push(item);
         }
         Ok(vec)
     }
 }
 
-impl<Length, Item> crate::Encoder for Vec<Length, Item>
+#[cfg(feature = "std")]
+impl<Length, Item> crate::ReadDecoder for Vec<Length, Item>
 where
-    Length: crate::Encoder<Decoded = usize>,
-    Item: crate::Encoder,
-    Item::Decoded: Sized,
+    Length: crate::ReadDecoder<Decoded = usize>,
+    Item: crate::ReadDecoder,
 {
     type Decoded = StdVec<Item::Decoded>;
 
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
-        let size = decoded.len();
-        self.length.encode(&size, encoded, offset)?;
-        for item in decoded.iter() {
-            self.item.encode(item, encoded, offset)?;
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let size = self.length.decode_from_with(reader, budget)?;
+        if size > self.max_length {
+            return Err(crate::DecodeError::LengthLimitExceeded);
         }
-        Ok(())
+        budget.charge(size, size.saturating_mul(size_of::<Item::Decoded>()))?;
+        let mut vec = StdVec::with_capacity(size.min(MAX_READ_PREALLOCATION));
+        for index in 0..size {
+            let item = self.item.decode_from_with(reader, budget)
+                .map_err(|error| error.in_index(index))?;
+            vec.push(item);
+        }
+        Ok(vec)
     }
 }
 
-impl<Length, Item> crate::Measurer for Vec<Length, Item>
+#[cfg(feature = "alloc")]
+impl<Length, Item> crate::Encoder for Vec<Length, Item>
 where
-    Length: crate::Measurer<Decoded = usize>,
-    Item: crate::Measurer,
+    Length: crate::Encoder<Decoded = usize>,
+    Item: crate::Encoder,
     Item::Decoded: Sized,
 {
     type Decoded = StdVec<Item::Decoded>;
 
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
         let size = decoded.len();
-        let size_measure = self.length.measure(&size)?;
-        let mut items_measure = 0;
+        self.length.encode(&size, sink)?;
         for item in decoded.iter() {
-            items_measure += self.item.measure(item)?;
+            self.item.encode(item, sink)?;
         }
-        Ok(size_measure + items_measure)
+        Ok(())
     }
 }
 
@@ -101,35 +143,81 @@ where
     type Decoded = &'decoded [u8];
 
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
-        if *offset > encoded.len() {
-            return Err(crate::DecodeError::InvalidData);
-        }
-        let remaining = &encoded[*offset..];
+        let remaining = encoded.get(*offset..).ok_or(crate::DecodeError::InvalidData)?;
         *offset = encoded.len();
         Ok(remaining)
     }
 }
 
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for Remaining {
+    type Decoded = StdVec<u8>;
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        // Read one byte past the budget so that running over it is detected rather than truncated.
+        let allowance = budget.max_bytes.saturating_add(1);
+        let mut remaining = StdVec::new();
+        reader.take(allowance as u64).read_to_end(&mut remaining)?;
+        budget.charge(0, remaining.len())?;
+        Ok(remaining)
+    }
+}
+
 impl crate::Encoder for Remaining {
     type Decoded = [u8];
 
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
-        let end = *offset + decoded.len();
-        if end > encoded.len() {
-            return Err(crate::EncodeError::BufferTooSmall);
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+        sink.write(decoded)
+    }
+}
+
+// Writes septets in wire order, flagging every one but the last as continued.
+fn encode_septets<S: crate::Sink + ?Sized>(septets: &[u8], sink: &mut S) -> Result<(), crate::EncodeError> {
+    let (&last, rest) = septets.split_last().expect("At least one septet");
+    for &septet in rest {
+        (septet | 0x80).encode(sink)?;
+    }
+    last.encode(sink)
+}
+
+// Collects up to `N` septets in wire order; a longer varint is invalid data.
+fn decode_septets<const N: usize>(encoded: &[u8], offset: &mut usize) -> Result<heapless::Vec<u8, N>, crate::DecodeError> {
+    let mut septets = heapless::Vec::<u8, N>::new();
+
+    loop {
+        let flagged_septet = u8::decode(encoded, offset)?;
+        let septet = flagged_septet & 0x7F;
+
+        septets.push(septet).map_err(|_| crate::DecodeError::InvalidData)?;
+
+        if flagged_septet & 0x80 == 0 {
+            break;
         }
-        encoded[*offset..end].copy_from_slice(decoded);
-        *offset = end;
-        Ok(())
     }
+
+    Ok(septets)
 }
 
-impl crate::Measurer for Remaining {
-    type Decoded = [u8];
+#[cfg(feature = "std")]
+fn read_septets<const N: usize, R: Read + ?Sized>(reader: &mut R) -> Result<heapless::Vec<u8, N>, crate::DecodeError> {
+    let mut septets = heapless::Vec::<u8, N>::new();
+
+    loop {
+        let flagged_septet = u8::decode_from(reader)?;
+        let septet = flagged_septet & 0x7F;
+
+        septets.push(septet).map_err(|_| crate::DecodeError::InvalidData)?;
 
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
-        Ok(decoded.len())
+        if flagged_septet & 0x80 == 0 {
+            break;
+        }
     }
+
+    Ok(septets)
 }
 
 #[derive(Copy, Clone)]
@@ -145,6 +233,10 @@ impl Default for U64BE {
     fn default() -> Self { Self::codec() }
 }
 
+impl Varint for U64BE {
+    const MAX_BYTES: usize = 10;
+}
+
 impl U64BE {
     fn into_septets_le(num: u64) -> [u8; 10] {
         let mut bits_from_lsb = (0..64).map(move |bit| num & (1 << bit) != 0);
@@ -200,20 +292,20 @@ impl U64BE {
 
 impl crate::Encoder for U64BE {
     type Decoded = u64;
-    fn encode(&self, &decoded: &u64, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
+    fn encode<S: crate::Sink + ?Sized>(&self, &decoded: &u64, sink: &mut S) -> Result<(), crate::EncodeError> {
         let septets_be = Self::into_septets_be(decoded);
         let skip = septets_be.iter().take_while(|&&b| b == 0).count();
         let trunc_septets_be = &septets_be[skip..];
 
         if trunc_septets_be.is_empty() {
-            return 0u8.encode(encoded, offset);
+            return 0u8.encode(sink);
         }
 
         for &septet in &trunc_septets_be[0..trunc_septets_be.len() - 1] {
-            (septet | 0x80).encode(encoded, offset)?;
+            (septet | 0x80).encode(sink)?;
         }
 
-        trunc_septets_be[trunc_septets_be.len() - 1].encode(encoded, offset)?;
+        trunc_septets_be[trunc_septets_be.len() - 1].encode(sink)?;
 
         Ok(())
     }
@@ -223,18 +315,23 @@ impl<'encoded> crate::Decoder<'encoded, '_> for U64BE {
     type Decoded = u64;
 
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<u64, crate::DecodeError> {
-        let mut septets_be = heapless::Vec::<u8, 10>::new();
+        let septets_be = decode_septets::<10>(encoded, offset)?;
+
+        let mut septets_le_array = [0u8; 10];
+        for (i, &septet) in septets_be.iter().rev().enumerate() {
+            septets_le_array[i] = septet;
+        }
 
-        loop {
-            let flagged_septet = u8::decode(encoded, offset)?;
-            let septet = flagged_septet & 0x7F;
+        Ok(U64BE::from_septets_le(septets_le_array))
+    }
+}
 
-            septets_be.push(septet).map_err(|_| crate::DecodeError::InvalidData)?;
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for U64BE {
+    type Decoded = u64;
 
-            if flagged_septet & 0x80 == 0 {
-                break;
-            }
-        }
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<u64, crate::DecodeError> {
+        let septets_be = read_septets::<10, _>(reader)?;
 
         let mut septets_le_array = [0u8; 10];
         for (i, &septet) in septets_be.iter().rev().enumerate() {
@@ -245,20 +342,492 @@ impl<'encoded> crate::Decoder<'encoded, '_> for U64BE {
     }
 }
 
-impl crate::Measurer for U64BE {
+// Unsigned LEB128, as used by protobuf, WebAssembly and DWARF: septets least-significant group first.
+#[derive(Copy, Clone)]
+pub struct U64LE;
+
+impl U64LE {
+    pub const fn codec() -> Self {
+        U64LE
+    }
+}
+
+impl Default for U64LE {
+    fn default() -> Self { Self::codec() }
+}
+
+impl Varint for U64LE {
+    const MAX_BYTES: usize = 10;
+}
+
+impl U64LE {
+    fn from_septets(septets_le: &[u8]) -> u64 {
+        let mut septets_le_array = [0u8; 10];
+        septets_le_array[..septets_le.len()].copy_from_slice(septets_le);
+        U64BE::from_septets_le(septets_le_array)
+    }
+}
+
+impl crate::Encoder for U64LE {
     type Decoded = u64;
-    fn measure(&self, &decoded: &u64) -> Result<usize, crate::EncodeError> {
-        let septets_be = Self::into_septets_be(decoded);
-        let skip = septets_be.iter().take_while(|&&b| b == 0).count();
-        let take = septets_be.len() - skip;
-        Ok(take.max(1))
+    fn encode<S: crate::Sink + ?Sized>(&self, &decoded: &u64, sink: &mut S) -> Result<(), crate::EncodeError> {
+        let septets_le = U64BE::into_septets_le(decoded);
+        let len = septets_le.iter().rposition(|&b| b != 0).map_or(1, |last| last + 1);
+        encode_septets(&septets_le[..len], sink)
     }
 }
 
-#[cfg(test)]
+impl<'encoded> crate::Decoder<'encoded, '_> for U64LE {
+    type Decoded = u64;
+
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<u64, crate::DecodeError> {
+        let septets_le = decode_septets::<10>(encoded, offset)?;
+        Ok(Self::from_septets(&septets_le))
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for U64LE {
+    type Decoded = u64;
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<u64, crate::DecodeError> {
+        let septets_le = read_septets::<10, _>(reader)?;
+        Ok(Self::from_septets(&septets_le))
+    }
+}
+
+// Signed LEB128: two's complement septets, least-significant group first, sign-extended from
+// bit 6 of the last septet.
+#[derive(Copy, Clone)]
+pub struct I64LE;
+
+impl I64LE {
+    pub const fn codec() -> Self {
+        I64LE
+    }
+}
+
+impl Default for I64LE {
+    fn default() -> Self { Self::codec() }
+}
+
+impl Varint for I64LE {
+    const MAX_BYTES: usize = 10;
+}
+
+impl I64LE {
+    fn from_septets(septets_le: &[u8]) -> i64 {
+        let value = U64LE::from_septets(septets_le) as i64;
+        let bits = septets_le.len() * 7;
+        if bits < 64 && septets_le.last().is_some_and(|&septet| septet & 0x40 != 0) {
+            value | (-1i64 << bits)
+        } else {
+            value
+        }
+    }
+}
+
+impl I64LE {
+    // The shortest septets that sign-extend back to `value`, least-significant first.
+    fn into_septets(value: i64) -> heapless::Vec<u8, 10> {
+        let mut septets_le = U64BE::into_septets_le(value as u64);
+        // The tenth septet carries only the top bit; the rest of it is sign fill.
+        if value < 0 {
+            septets_le[9] |= 0x7e;
+        }
+        let len = (1..=septets_le.len())
+            .find(|&len| Self::from_septets(&septets_le[..len]) == value)
+            .expect("Ten septets hold any i64");
+        heapless::Vec::from_slice(&septets_le[..len]).expect("At most 10 septets")
+    }
+}
+
+impl crate::Encoder for I64LE {
+    type Decoded = i64;
+    fn encode<S: crate::Sink + ?Sized>(&self, &decoded: &i64, sink: &mut S) -> Result<(), crate::EncodeError> {
+        encode_septets(&Self::into_septets(decoded), sink)
+    }
+}
+
+impl<'encoded> crate::Decoder<'encoded, '_> for I64LE {
+    type Decoded = i64;
+
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<i64, crate::DecodeError> {
+        let septets_le = decode_septets::<10>(encoded, offset)?;
+        Ok(Self::from_septets(&septets_le))
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for I64LE {
+    type Decoded = i64;
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<i64, crate::DecodeError> {
+        let septets_le = read_septets::<10, _>(reader)?;
+        Ok(Self::from_septets(&septets_le))
+    }
+}
+
+// The two's complement septets of `I64LE`, most-significant group first as in `U64BE`; the sign
+// is taken from bit 6 of the first septet.
+#[derive(Copy, Clone)]
+pub struct I64BE;
+
+impl I64BE {
+    pub const fn codec() -> Self {
+        I64BE
+    }
+
+    fn from_septets_be(septets_be: &[u8]) -> i64 {
+        let mut septets_le = heapless::Vec::<u8, 10>::from_slice(septets_be).expect("At most 10 septets");
+        septets_le.reverse();
+        I64LE::from_septets(&septets_le)
+    }
+}
+
+impl Default for I64BE {
+    fn default() -> Self { Self::codec() }
+}
+
+impl Varint for I64BE {
+    const MAX_BYTES: usize = 10;
+}
+
+impl crate::Encoder for I64BE {
+    type Decoded = i64;
+    fn encode<S: crate::Sink + ?Sized>(&self, &decoded: &i64, sink: &mut S) -> Result<(), crate::EncodeError> {
+        let mut septets_be = I64LE::into_septets(decoded);
+        septets_be.reverse();
+        encode_septets(&septets_be, sink)
+    }
+}
+
+impl<'encoded> crate::Decoder<'encoded, '_> for I64BE {
+    type Decoded = i64;
+
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<i64, crate::DecodeError> {
+        let septets_be = decode_septets::<10>(encoded, offset)?;
+        Ok(Self::from_septets_be(&septets_be))
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for I64BE {
+    type Decoded = i64;
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<i64, crate::DecodeError> {
+        let septets_be = read_septets::<10, _>(reader)?;
+        Ok(Self::from_septets_be(&septets_be))
+    }
+}
+
+// Zigzag maps signed integers onto unsigned ones so small magnitudes stay short, as protobuf's
+// `sint32`/`sint64` do, and then encodes them with an unsigned varint.
+macro_rules! define_zigzag {
+    ($name:ident, $ty:ty, $uty:ty, $base:ident) => {
+        #[derive(Copy, Clone)]
+        pub struct $name;
+
+        impl $name {
+            pub const fn codec() -> Self {
+                $name
+            }
+
+            fn zigzag(value: $ty) -> $uty {
+                ((value << 1) ^ (value >> (<$ty>::BITS - 1))) as $uty
+            }
+
+            fn unzigzag(value: $uty) -> $ty {
+                (value >> 1) as $ty ^ -((value & 1) as $ty)
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self { Self::codec() }
+        }
+
+        impl Varint for $name {
+            const MAX_BYTES: usize = <$base as Varint>::MAX_BYTES;
+        }
+
+        impl<'encoded> crate::Decoder<'encoded, '_> for $name {
+            type Decoded = $ty;
+            fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<$ty, crate::DecodeError> {
+                let unsigned_value = $base.decode(encoded, offset)?;
+                Ok(Self::unzigzag(unsigned_value))
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl crate::ReadDecoder for $name {
+            type Decoded = $ty;
+            fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<$ty, crate::DecodeError> {
+                let unsigned_value = crate::ReadDecoder::decode_from(&$base, reader)?;
+                Ok(Self::unzigzag(unsigned_value))
+            }
+        }
+
+        impl crate::Encoder for $name {
+            type Decoded = $ty;
+            fn encode<S: crate::Sink + ?Sized>(&self, &decoded: &$ty, sink: &mut S) -> Result<(), crate::EncodeError> {
+                $base.encode(&Self::zigzag(decoded), sink)
+            }
+        }
+    };
+}
+
+define_zigzag!(I64ZigZagBE, i64, u64, U64BE);
+define_zigzag!(I64ZigZagLE, i64, u64, U64LE);
+
+// 128-bit varints need up to 19 septets; the most significant one carries only two bits.
+#[derive(Copy, Clone)]
+pub struct U128BE;
+
+impl U128BE {
+    pub const fn codec() -> Self {
+        U128BE
+    }
+}
+
+impl Default for U128BE {
+    fn default() -> Self { Self::codec() }
+}
+
+impl Varint for U128BE {
+    const MAX_BYTES: usize = 19;
+}
+
+impl U128BE {
+    fn into_septets_le(num: u128) -> [u8; 19] {
+        let mut septets_le = [0u8; 19];
+        for (index, septet) in septets_le.iter_mut().enumerate() {
+            *septet = ((num >> (7 * index)) & 0x7f) as u8;
+        }
+        septets_le
+    }
+
+    // Bits past the 128th are dropped, as `U64BE` drops those past the 64th.
+    fn from_septets_le(septets_le: &[u8]) -> u128 {
+        septets_le.iter().enumerate().fold(0, |value, (index, &septet)| {
+            value | (septet as u128).checked_shl(7 * index as u32).unwrap_or(0)
+        })
+    }
+
+    fn from_septets_be(septets_be: &[u8]) -> u128 {
+        let mut septets_le = heapless::Vec::<u8, 19>::from_slice(septets_be).expect("At most 19 septets");
+        septets_le.reverse();
+        Self::from_septets_le(&septets_le)
+    }
+}
+
+impl crate::Encoder for U128BE {
+    type Decoded = u128;
+    fn encode<S: crate::Sink + ?Sized>(&self, &decoded: &u128, sink: &mut S) -> Result<(), crate::EncodeError> {
+        let mut septets_be = Self::into_septets_le(decoded);
+        septets_be.reverse();
+        let skip = septets_be.iter().take_while(|&&b| b == 0).count().min(septets_be.len() - 1);
+        encode_septets(&septets_be[skip..], sink)
+    }
+}
+
+impl<'encoded> crate::Decoder<'encoded, '_> for U128BE {
+    type Decoded = u128;
+
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<u128, crate::DecodeError> {
+        let septets_be = decode_septets::<19>(encoded, offset)?;
+        Ok(Self::from_septets_be(&septets_be))
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for U128BE {
+    type Decoded = u128;
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<u128, crate::DecodeError> {
+        let septets_be = read_septets::<19, _>(reader)?;
+        Ok(Self::from_septets_be(&septets_be))
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct U128LE;
+
+impl U128LE {
+    pub const fn codec() -> Self {
+        U128LE
+    }
+}
+
+impl Default for U128LE {
+    fn default() -> Self { Self::codec() }
+}
+
+impl Varint for U128LE {
+    const MAX_BYTES: usize = 19;
+}
+
+impl crate::Encoder for U128LE {
+    type Decoded = u128;
+    fn encode<S: crate::Sink + ?Sized>(&self, &decoded: &u128, sink: &mut S) -> Result<(), crate::EncodeError> {
+        let septets_le = U128BE::into_septets_le(decoded);
+        let len = septets_le.iter().rposition(|&b| b != 0).map_or(1, |last| last + 1);
+        encode_septets(&septets_le[..len], sink)
+    }
+}
+
+impl<'encoded> crate::Decoder<'encoded, '_> for U128LE {
+    type Decoded = u128;
+
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<u128, crate::DecodeError> {
+        let septets_le = decode_septets::<19>(encoded, offset)?;
+        Ok(U128BE::from_septets_le(&septets_le))
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for U128LE {
+    type Decoded = u128;
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<u128, crate::DecodeError> {
+        let septets_le = read_septets::<19, _>(reader)?;
+        Ok(U128BE::from_septets_le(&septets_le))
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct I128LE;
+
+impl I128LE {
+    pub const fn codec() -> Self {
+        I128LE
+    }
+}
+
+impl Default for I128LE {
+    fn default() -> Self { Self::codec() }
+}
+
+impl Varint for I128LE {
+    const MAX_BYTES: usize = 19;
+}
+
+impl I128LE {
+    fn from_septets(septets_le: &[u8]) -> i128 {
+        let value = U128BE::from_septets_le(septets_le) as i128;
+        let bits = septets_le.len() * 7;
+        if bits < 128 && septets_le.last().is_some_and(|&septet| septet & 0x40 != 0) {
+            value | (-1i128 << bits)
+        } else {
+            value
+        }
+    }
+}
+
+impl I128LE {
+    // The shortest septets that sign-extend back to `value`, least-significant first.
+    fn into_septets(value: i128) -> heapless::Vec<u8, 19> {
+        let mut septets_le = U128BE::into_septets_le(value as u128);
+        // The nineteenth septet carries only the top two bits; the rest of it is sign fill.
+        if value < 0 {
+            septets_le[18] |= 0x7c;
+        }
+        let len = (1..=septets_le.len())
+            .find(|&len| Self::from_septets(&septets_le[..len]) == value)
+            .expect("Nineteen septets hold any i128");
+        heapless::Vec::from_slice(&septets_le[..len]).expect("At most 19 septets")
+    }
+}
+
+impl crate::Encoder for I128LE {
+    type Decoded = i128;
+    fn encode<S: crate::Sink + ?Sized>(&self, &decoded: &i128, sink: &mut S) -> Result<(), crate::EncodeError> {
+        encode_septets(&Self::into_septets(decoded), sink)
+    }
+}
+
+impl<'encoded> crate::Decoder<'encoded, '_> for I128LE {
+    type Decoded = i128;
+
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<i128, crate::DecodeError> {
+        let septets_le = decode_septets::<19>(encoded, offset)?;
+        Ok(Self::from_septets(&septets_le))
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for I128LE {
+    type Decoded = i128;
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<i128, crate::DecodeError> {
+        let septets_le = read_septets::<19, _>(reader)?;
+        Ok(Self::from_septets(&septets_le))
+    }
+}
+
+// The two's complement septets of `I128LE`, most-significant group first as in `U128BE`; the
+// sign is taken from bit 6 of the first septet.
+#[derive(Copy, Clone)]
+pub struct I128BE;
+
+impl I128BE {
+    pub const fn codec() -> Self {
+        I128BE
+    }
+
+    fn from_septets_be(septets_be: &[u8]) -> i128 {
+        let mut septets_le = heapless::Vec::<u8, 19>::from_slice(septets_be).expect("At most 19 septets");
+        septets_le.reverse();
+        I128LE::from_septets(&septets_le)
+    }
+}
+
+impl Default for I128BE {
+    fn default() -> Self { Self::codec() }
+}
+
+impl Varint for I128BE {
+    const MAX_BYTES: usize = 19;
+}
+
+impl crate::Encoder for I128BE {
+    type Decoded = i128;
+    fn encode<S: crate::Sink + ?Sized>(&self, &decoded: &i128, sink: &mut S) -> Result<(), crate::EncodeError> {
+        let mut septets_be = I128LE::into_septets(decoded);
+        septets_be.reverse();
+        encode_septets(&septets_be, sink)
+    }
+}
+
+impl<'encoded> crate::Decoder<'encoded, '_> for I128BE {
+    type Decoded = i128;
+
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<i128, crate::DecodeError> {
+        let septets_be = decode_septets::<19>(encoded, offset)?;
+        Ok(Self::from_septets_be(&septets_be))
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for I128BE {
+    type Decoded = i128;
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<i128, crate::DecodeError> {
+        let septets_be = read_septets::<19, _>(reader)?;
+        Ok(Self::from_septets_be(&septets_be))
+    }
+}
+
+define_zigzag!(I128ZigZagBE, i128, u128, U128BE);
+define_zigzag!(I128ZigZagLE, i128, u128, U128LE);
+
+#[cfg(all(test, feature = "std"))]
 mod test {
     use crate::prelude::EncoderToVec;
     use crate::Decoder as _;
+    use crate::ReadDecoder as _;
+    use crate::Encoder as _;
+    use crate::Measurer as _;
+    use crate::sink::SliceSink;
 
     use super::*;
 
@@ -300,14 +869,287 @@ mod test {
             let encoded = U64BE.encode_to_vec(num).expect("Encoding failed");
             assert_eq!(&encoded, encoded_fixture, "Encoding failed for {}", num);
 
+            let mut buffer = vec![0u8; encoded.len()];
+            U64BE.encode(num, &mut SliceSink::new(&mut buffer, &mut 0)).expect("Slice encoding failed");
+            assert_eq!(&buffer, encoded_fixture, "Slice encoding failed for {}", num);
+
             let decoded = U64BE.decode(&encoded, &mut 0).expect("Decoding failed");
             assert_eq!(&decoded, num, "Decoding failed for {:?}", encoded);
+
+            let decoded = U64BE.decode_from(&mut encoded.as_slice()).expect("Reading failed");
+            assert_eq!(&decoded, num, "Reading failed for {:?}", encoded);
+        }
+    }
+
+    #[test]
+    fn test_u64le() {
+        let fixtures = [
+            (0u64,      vec![0x00]),
+            (1u64,      vec![0x01]),
+            (127u64,    vec![0x7f]),
+            (128u64,    vec![0x80, 0x01]),
+            (300u64,    vec![0xac, 0x02]),
+            (16384u64,  vec![0x80, 0x80, 0x01]),
+            (624485u64, vec![0xe5, 0x8e, 0x26]),
+            (u64::MAX,  vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]),
+        ];
+
+        for (num, encoded_fixture) in fixtures.iter() {
+            let encoded = U64LE.encode_to_vec(num).expect("Encoding failed");
+            assert_eq!(&encoded, encoded_fixture, "Encoding failed for {}", num);
+            assert_eq!(U64LE.measure(num).expect("Measuring failed"), encoded_fixture.len());
+
+            let decoded = U64LE.decode(&encoded, &mut 0).expect("Decoding failed");
+            assert_eq!(&decoded, num, "Decoding failed for {:?}", encoded);
+
+            let decoded = U64LE.decode_from(&mut encoded.as_slice()).expect("Reading failed");
+            assert_eq!(&decoded, num, "Reading failed for {:?}", encoded);
+        }
+
+        assert_eq!(U32LE.encode_to_vec(&300).expect("Encoding failed"), [0xac, 0x02]);
+        assert_eq!(USizeLE.decode(&[0xac, 0x02], &mut 0).expect("Decoding failed"), 300);
+        assert!(U16LE.decode(&[0x80, 0x80, 0x04], &mut 0).is_err());
+        assert!(U64LE.decode(&[0x80; 11], &mut 0).is_err());
+    }
+
+    #[test]
+    fn test_i64le() {
+        let fixtures = [
+            (0i64,          vec![0x00]),
+            (2i64,          vec![0x02]),
+            (-2i64,         vec![0x7e]),
+            (63i64,         vec![0x3f]),
+            (64i64,         vec![0xc0, 0x00]),
+            (-64i64,        vec![0x40]),
+            (-65i64,        vec![0xbf, 0x7f]),
+            (127i64,        vec![0xff, 0x00]),
+            (-128i64,       vec![0x80, 0x7f]),
+            (-123456i64,    vec![0xc0, 0xbb, 0x78]),
+            (i64::MAX,      vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]),
+            (i64::MIN,      vec![0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f]),
+        ];
+
+        for (num, encoded_fixture) in fixtures.iter() {
+            let encoded = I64LE.encode_to_vec(num).expect("Encoding failed");
+            assert_eq!(&encoded, encoded_fixture, "Encoding failed for {}", num);
+
+            let decoded = I64LE.decode(&encoded, &mut 0).expect("Decoding failed");
+            assert_eq!(&decoded, num, "Decoding failed for {:?}", encoded);
+
+            let decoded = I64LE.decode_from(&mut encoded.as_slice()).expect("Reading failed");
+            assert_eq!(&decoded, num, "Reading failed for {:?}", encoded);
+        }
+
+        assert_eq!(I32LE.encode_to_vec(&-123456).expect("Encoding failed"), [0xc0, 0xbb, 0x78]);
+        assert_eq!(ISizeLE.decode(&[0x7e], &mut 0).expect("Decoding failed"), -2);
+        assert!(I16LE.decode(&[0xc0, 0xbb, 0x78], &mut 0).is_err());
+    }
+
+    #[test]
+    fn test_i64be() {
+        let fixtures = [
+            (0i64,          vec![0x00]),
+            (2i64,          vec![0x02]),
+            (-2i64,         vec![0x7e]),
+            (63i64,         vec![0x3f]),
+            (64i64,         vec![0x80, 0x40]),
+            (-64i64,        vec![0x40]),
+            (-65i64,        vec![0xff, 0x3f]),
+            (127i64,        vec![0x80, 0x7f]),
+            (-128i64,       vec![0xff, 0x00]),
+            (-123456i64,    vec![0xf8, 0xbb, 0x40]),
+            (i64::MAX,      vec![0x80, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]),
+            (i64::MIN,      vec![0xff, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]),
+        ];
+
+        for (num, encoded_fixture) in fixtures.iter() {
+            let encoded = I64BE.encode_to_vec(num).expect("Encoding failed");
+            assert_eq!(&encoded, encoded_fixture, "Encoding failed for {}", num);
+
+            let decoded = I64BE.decode(&encoded, &mut 0).expect("Decoding failed");
+            assert_eq!(&decoded, num, "Decoding failed for {:?}", encoded);
+
+            let decoded = I64BE.decode_from(&mut encoded.as_slice()).expect("Reading failed");
+            assert_eq!(&decoded, num, "Reading failed for {:?}", encoded);
+        }
+
+        assert_eq!(I32BE.encode_to_vec(&-123456).expect("Encoding failed"), [0xf8, 0xbb, 0x40]);
+        assert_eq!(ISizeBE.decode(&[0x7e], &mut 0).expect("Decoding failed"), -2);
+        assert!(I16BE.decode(&[0xf8, 0xbb, 0x40], &mut 0).is_err());
+    }
+
+    #[test]
+    fn test_zigzag() {
+        let fixtures = [
+            (0i64, 0u64),
+            (-1i64, 1u64),
+            (1i64, 2u64),
+            (-2i64, 3u64),
+            (150i64, 300u64),
+            (i32::MAX as i64, 4294967294u64),
+            (i32::MIN as i64, 4294967295u64),
+            (i64::MAX, u64::MAX - 1),
+            (i64::MIN, u64::MAX),
+        ];
+
+        for (num, zigzagged) in fixtures.iter() {
+            let encoded = I64ZigZagLE.encode_to_vec(num).expect("Encoding failed");
+            assert_eq!(encoded, U64LE.encode_to_vec(zigzagged).expect("Encoding failed"), "LE encoding failed for {}", num);
+            assert_eq!(&I64ZigZagLE.decode(&encoded, &mut 0).expect("Decoding failed"), num);
+
+            let encoded = I64ZigZagBE.encode_to_vec(num).expect("Encoding failed");
+            assert_eq!(encoded, U64BE.encode_to_vec(zigzagged).expect("Encoding failed"), "BE encoding failed for {}", num);
+            assert_eq!(&I64ZigZagBE.decode_from(&mut encoded.as_slice()).expect("Reading failed"), num);
         }
+
+        assert_eq!(I32ZigZagLE.encode_to_vec(&150).expect("Encoding failed"), [0xac, 0x02]);
+        assert_eq!(I16ZigZagBE.decode(&[0x01], &mut 0).expect("Decoding failed"), -1);
+        assert_eq!(ISizeZigZagLE.measure(&-65).expect("Measuring failed"), 2);
+        assert!(I16ZigZagLE.decode(&[0xac, 0x80, 0x04], &mut 0).is_err());
+    }
+
+    #[test]
+    fn test_128_bit() {
+        let fixtures = [
+            (0u128, vec![0x00], vec![0x00]),
+            (300u128, vec![0x82, 0x2c], vec![0xac, 0x02]),
+            (u64::MAX as u128 + 1, vec![0x82, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00], vec![0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x02]),
+        ];
+        for (num, be_fixture, le_fixture) in fixtures.iter() {
+            assert_eq!(&U128BE.encode_to_vec(num).expect("Encoding failed"), be_fixture, "BE encoding failed for {}", num);
+            assert_eq!(&U128LE.encode_to_vec(num).expect("Encoding failed"), le_fixture, "LE encoding failed for {}", num);
+        }
+
+        for num in [0u128, 1, 127, 128, u64::MAX as u128, 1 << 100, u128::MAX] {
+            let len = (128 - num.leading_zeros() as usize).div_ceil(7).max(1);
+            let encoded = U128BE.encode_to_vec(&num).expect("Encoding failed");
+            assert_eq!(encoded.len(), len, "BE length failed for {}", num);
+            assert_eq!(U128BE.measure(&num).expect("Measuring failed"), len);
+            assert_eq!(U128BE.decode(&encoded, &mut 0).expect("Decoding failed"), num);
+            assert_eq!(U128BE.decode_from(&mut encoded.as_slice()).expect("Reading failed"), num);
+
+            let encoded = U128LE.encode_to_vec(&num).expect("Encoding failed");
+            assert_eq!(encoded.len(), len, "LE length failed for {}", num);
+            assert_eq!(U128LE.measure(&num).expect("Measuring failed"), len);
+            assert_eq!(U128LE.decode(&encoded, &mut 0).expect("Decoding failed"), num);
+            assert_eq!(U128LE.decode_from(&mut encoded.as_slice()).expect("Reading failed"), num);
+        }
+        assert_eq!(U128LE.encode_to_vec(&u128::MAX).expect("Encoding failed").last(), Some(&0x03));
+
+        for num in [0i128, -1, 63, -64, -65, i64::MIN as i128, i128::MAX, i128::MIN] {
+            let encoded = I128LE.encode_to_vec(&num).expect("Encoding failed");
+            assert_eq!(I128LE.measure(&num).expect("Measuring failed"), encoded.len());
+            assert_eq!(I128LE.decode(&encoded, &mut 0).expect("Decoding failed"), num);
+            assert_eq!(I128LE.decode_from(&mut encoded.as_slice()).expect("Reading failed"), num);
+            Strict::codec(I128LE).decode(&encoded, &mut 0).expect("Canonical input rejected");
+
+            let encoded_be = I128BE.encode_to_vec(&num).expect("Encoding failed");
+            assert_eq!(encoded_be.iter().map(|byte| byte & 0x7f).rev().collect::<std::vec::Vec<_>>(), encoded.iter().map(|byte| byte & 0x7f).collect::<std::vec::Vec<_>>());
+            assert_eq!(I128BE.measure(&num).expect("Measuring failed"), encoded_be.len());
+            assert_eq!(I128BE.decode(&encoded_be, &mut 0).expect("Decoding failed"), num);
+            assert_eq!(I128BE.decode_from(&mut encoded_be.as_slice()).expect("Reading failed"), num);
+            Strict::codec(I128BE).decode(&encoded_be, &mut 0).expect("Canonical input rejected");
+
+            let encoded = I128ZigZagBE.encode_to_vec(&num).expect("Encoding failed");
+            assert_eq!(I128ZigZagBE.measure(&num).expect("Measuring failed"), encoded.len());
+            assert_eq!(I128ZigZagBE.decode(&encoded, &mut 0).expect("Decoding failed"), num);
+
+            let encoded = I128ZigZagLE.encode_to_vec(&num).expect("Encoding failed");
+            assert_eq!(I128ZigZagLE.measure(&num).expect("Measuring failed"), encoded.len());
+            assert_eq!(I128ZigZagLE.decode_from(&mut encoded.as_slice()).expect("Reading failed"), num);
+        }
+        assert_eq!(I128LE.encode_to_vec(&i64::MIN.into()).expect("Encoding failed"), I64LE.encode_to_vec(&i64::MIN).expect("Encoding failed"));
+        assert_eq!(I128ZigZagLE.encode_to_vec(&-2).expect("Encoding failed"), [0x03]);
+        assert_eq!(I128BE.encode_to_vec(&-65).expect("Encoding failed"), [0xff, 0x3f]);
+        assert_eq!(I128BE.encode_to_vec(&64).expect("Encoding failed"), [0x80, 0x40]);
+
+        assert!(U128BE.decode(&[0x80; 20], &mut 0).is_err());
+        assert!(matches!(Strict::codec(U128LE).decode(&[0x80, 0x00], &mut 0), Err(crate::DecodeError::NonCanonical)));
+    }
+
+    #[test]
+    fn test_strict() {
+        fn assert_non_canonical<T: core::fmt::Debug>(result: Result<T, crate::DecodeError>) {
+            let error = result.expect_err("Non-canonical input accepted");
+            assert!(matches!(error, crate::DecodeError::NonCanonical), "Unexpected error: {:?}", error);
+        }
+
+        assert_non_canonical(Strict::codec(U64BE).decode(&[0x80, 0x01], &mut 0));
+        assert_non_canonical(Strict::codec(U64BE).decode(&[0x83, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f], &mut 0));
+        assert_non_canonical(Strict::codec(U64LE).decode(&[0x81, 0x00], &mut 0));
+        assert_non_canonical(Strict::codec(U64LE).decode(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f], &mut 0));
+        assert_non_canonical(Strict::codec(I64LE).decode(&[0xff, 0x7f], &mut 0));
+        assert_non_canonical(Strict::codec(U16BE).decode(&[0x80, 0x80, 0x05], &mut 0));
+        assert_non_canonical(Strict::codec(I32ZigZagLE).decode_from(&mut [0x80, 0x00].as_slice()));
+
+        // One past the narrowed type's maximum is a valid 64-bit varint but not a canonical one.
+        macro_rules! assert_narrowed {
+            ($($codec:ident: $ty:ty => $base:ident as $wide:ty),* $(,)?) => {$(
+                let encoded = $base.encode_to_vec(&(<$ty>::MAX as $wide + 1)).expect("Encoding failed");
+                assert_non_canonical(Strict::codec($codec).decode(&encoded, &mut 0));
+                assert_non_canonical(Strict::codec($codec).decode_from(&mut encoded.as_slice()));
+            )*};
+        }
+        assert_narrowed!(
+            U32BE: u32 => U64BE as u64,
+            U16BE: u16 => U64BE as u64,
+            U32LE: u32 => U64LE as u64,
+            U16LE: u16 => U64LE as u64,
+            I32BE: i32 => I64BE as i64,
+            I16BE: i16 => I64BE as i64,
+            I32LE: i32 => I64LE as i64,
+            I16LE: i16 => I64LE as i64,
+            I32ZigZagBE: i32 => I64ZigZagBE as i64,
+            I16ZigZagBE: i16 => I64ZigZagBE as i64,
+            I32ZigZagLE: i32 => I64ZigZagLE as i64,
+            I16ZigZagLE: i16 => I64ZigZagLE as i64,
+        );
+
+        for num in [0u64, 1, 127, 128, 300, u64::MAX] {
+            let encoded = U64BE.encode_to_vec(&num).expect("Encoding failed");
+            assert_eq!(Strict::codec(U64BE).decode(&encoded, &mut 0).expect("Canonical input rejected"), num);
+            assert_eq!(Strict::codec(U64BE).decode_from(&mut encoded.as_slice()).expect("Canonical input rejected"), num);
+
+            let encoded = U64LE.encode_to_vec(&num).expect("Encoding failed");
+            assert_eq!(Strict::codec(U64LE).decode(&encoded, &mut 0).expect("Canonical input rejected"), num);
+        }
+
+        let codec = Strict::codec(U64LE).with_max_bytes(2);
+        assert_eq!(codec.decode(&[0xac, 0x02], &mut 0).expect("Decoding failed"), 300);
+        let error = codec.decode(&[0x80, 0x80, 0x01], &mut 0).expect_err("Long varint accepted");
+        assert!(matches!(error, crate::DecodeError::LengthLimitExceeded));
+        assert!(codec.encode_to_vec(&16384).is_err());
+        assert_eq!(codec.encode_to_vec(&300).expect("Encoding failed"), [0xac, 0x02]);
+    }
+
+    #[test]
+    fn test_incomplete_input() {
+        let slice = Slice::<USizeBE>::default();
+        let encoded = [5, 1, 2];
+
+        let mut offset = 0;
+        let error = slice.decode(&encoded, &mut offset).expect_err("Decoding a short slice succeeded");
+        assert!(matches!(error, crate::DecodeError::Incomplete { needed: Some(3) }));
+
+        let mut offset = 0;
+        let progress = slice.try_decode(&encoded, &mut offset).expect("Short slice reported as malformed");
+        assert_eq!(progress, crate::Progress::Incomplete { needed: Some(3) });
+        assert_eq!(offset, 0);
+
+        let progress = slice.try_decode(&[3, 1, 2, 3], &mut offset).expect("Decoding failed");
+        assert_eq!(progress, crate::Progress::Complete(&[1, 2, 3][..]));
+        assert_eq!(offset, 4);
+
+        let progress = U64BE.try_decode(&[0x81], &mut 0).expect("Truncated varint reported as malformed");
+        assert_eq!(progress, crate::Progress::Incomplete { needed: Some(1) });
+
+        let error = str::Str::<USizeBE>::default().try_decode(&[1, 0xff], &mut 0).expect_err("Invalid UTF-8 decoded");
+        assert!(matches!(error, crate::DecodeError::InvalidData));
     }
 }
 
-macro_rules! define_u_be {
-    ($name:ident, $ty:ty) => {
+macro_rules! define_u_var {
+    ($name:ident, $ty:ty, $base:ident) => {
         pub struct $name;
 
         impl $name {
@@ -320,10 +1162,24 @@ macro_rules! define_u_be {
             fn default() -> Self { Self::codec() }
         }
 
+        impl Varint for $name {
+            const MAX_BYTES: usize = <$base as Varint>::MAX_BYTES;
+        }
+
         impl<'encoded> crate::Decoder<'encoded, '_> for $name {
             type Decoded = $ty;
             fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<$ty, crate::DecodeError> {
-                let u64_value = U64BE.decode(encoded, offset)?;
+                let u64_value = $base.decode(encoded, offset)?;
+                let val: $ty = u64_value.try_into().map_err(|_| crate::DecodeError::ConversionFailure)?;
+                Ok(val)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl crate::ReadDecoder for $name {
+            type Decoded = $ty;
+            fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<$ty, crate::DecodeError> {
+                let u64_value = crate::ReadDecoder::decode_from(&$base, reader)?;
                 let val: $ty = u64_value.try_into().map_err(|_| crate::DecodeError::ConversionFailure)?;
                 Ok(val)
             }
@@ -331,25 +1187,220 @@ macro_rules! define_u_be {
 
         impl crate::Encoder for $name {
             type Decoded = $ty;
-            fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
+            fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
                 let u64_value = *decoded as u64;
-                U64BE.encode(&u64_value, encoded, offset)
+                $base.encode(&u64_value, sink)
+            }
+        }
+    };
+}
+
+define_u_var!(USizeBE, usize, U64BE);
+define_u_var!(U32BE, u32, U64BE);
+define_u_var!(U16BE, u16, U64BE);
+define_u_var!(USizeLE, usize, U64LE);
+define_u_var!(U32LE, u32, U64LE);
+define_u_var!(U16LE, u16, U64LE);
+
+macro_rules! define_i_var {
+    ($name:ident, $ty:ty, $base:ident) => {
+        pub struct $name;
+
+        impl $name {
+            pub const fn codec() -> Self {
+                $name
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self { Self::codec() }
+        }
+
+        impl Varint for $name {
+            const MAX_BYTES: usize = <$base as Varint>::MAX_BYTES;
+        }
+
+        impl<'encoded> crate::Decoder<'encoded, '_> for $name {
+            type Decoded = $ty;
+            fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<$ty, crate::DecodeError> {
+                let i64_value = $base.decode(encoded, offset)?;
+                let val: $ty = i64_value.try_into().map_err(|_| crate::DecodeError::ConversionFailure)?;
+                Ok(val)
             }
         }
 
-        impl crate::Measurer for $name {
+        #[cfg(feature = "std")]
+        impl crate::ReadDecoder for $name {
             type Decoded = $ty;
-            fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
-                let u64_value = *decoded as u64;
-                U64BE.measure(&u64_value)
+            fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<$ty, crate::DecodeError> {
+                let i64_value = crate::ReadDecoder::decode_from(&$base, reader)?;
+                let val: $ty = i64_value.try_into().map_err(|_| crate::DecodeError::ConversionFailure)?;
+                Ok(val)
+            }
+        }
+
+        impl crate::Encoder for $name {
+            type Decoded = $ty;
+            fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+                let i64_value = *decoded as i64;
+                $base.encode(&i64_value, sink)
             }
         }
     };
 }
 
-define_u_be!(USizeBE, usize);
-define_u_be!(U32BE, u32);
-define_u_be!(U16BE, u16);
+define_i_var!(ISizeBE, isize, I64BE);
+define_i_var!(I32BE, i32, I64BE);
+define_i_var!(I16BE, i16, I64BE);
+define_i_var!(ISizeLE, isize, I64LE);
+define_i_var!(I32LE, i32, I64LE);
+define_i_var!(I16LE, i16, I64LE);
+define_i_var!(ISizeZigZagBE, isize, I64ZigZagBE);
+define_i_var!(I32ZigZagBE, i32, I64ZigZagBE);
+define_i_var!(I16ZigZagBE, i16, I64ZigZagBE);
+define_i_var!(ISizeZigZagLE, isize, I64ZigZagLE);
+define_i_var!(I32ZigZagLE, i32, I64ZigZagLE);
+define_i_var!(I16ZigZagLE, i16, I64ZigZagLE);
+
+// Implemented by the variable-length integer codecs, whose encodings never exceed `MAX_BYTES`.
+pub trait Varint {
+    const MAX_BYTES: usize;
+}
+
+// Longest varint `Strict` can compare; every `Varint::MAX_BYTES` fits.
+const MAX_VARINT_BYTES: usize = 32;
+
+// Accepts only the canonical encoding of each value: overlong groups and bits past the decoded
+// type's width are rejected with `DecodeError::NonCanonical`, so every value has exactly one
+// accepted byte string.
+#[derive(Copy, Clone)]
+pub struct Strict<Codec> {
+    pub codec: Codec,
+    // Checked once the inner codec has decoded the whole varint, so a longer encoding is rejected
+    // only after up to `Codec::MAX_BYTES` bytes have been consumed.
+    pub max_bytes: usize,
+}
+
+impl<Codec: Varint> Strict<Codec> {
+    pub const fn codec(codec: Codec) -> Self {
+        Self { codec, max_bytes: Codec::MAX_BYTES }
+    }
+}
+
+impl<Codec> Strict<Codec> {
+    pub fn with_max_bytes(self, max_bytes: usize) -> Self {
+        Self { max_bytes, ..self }
+    }
+
+    fn canonical(&self, decoded: &Codec::Decoded, buffer: &mut [u8; MAX_VARINT_BYTES]) -> Result<usize, crate::EncodeError>
+    where
+        Codec: crate::Encoder,
+    {
+        let mut len = 0;
+        self.codec.encode(decoded, &mut crate::sink::SliceSink::new(buffer, &mut len))?;
+        Ok(len)
+    }
+
+    // The narrowed codecs report values past their type's width as `ConversionFailure`; no
+    // canonical encoding of the decoded type produces those bits.
+    fn narrowed(error: crate::DecodeError) -> crate::DecodeError {
+        match error {
+            crate::DecodeError::ConversionFailure => crate::DecodeError::NonCanonical,
+            error => error,
+        }
+    }
+
+    fn check(&self, consumed: &[u8], decoded: &Codec::Decoded) -> Result<(), crate::DecodeError>
+    where
+        Codec: crate::Encoder,
+    {
+        if consumed.len() > self.max_bytes {
+            return Err(crate::DecodeError::LengthLimitExceeded);
+        }
+        let mut buffer = [0u8; MAX_VARINT_BYTES];
+        let len = self.canonical(decoded, &mut buffer).map_err(|_| crate::DecodeError::NonCanonical)?;
+        if buffer[..len] != *consumed {
+            return Err(crate::DecodeError::NonCanonical);
+        }
+        Ok(())
+    }
+}
+
+impl<Codec> Default for Strict<Codec>
+where
+    Codec: Varint + Default,
+{
+    fn default() -> Self { Self::codec(Codec::default()) }
+}
+
+impl<Codec: Varint> Varint for Strict<Codec> {
+    const MAX_BYTES: usize = Codec::MAX_BYTES;
+}
+
+impl<'encoded, 'decoded, Codec> crate::Decoder<'encoded, 'decoded> for Strict<Codec>
+where
+    Codec: crate::Decoder<'encoded, 'decoded> + crate::Encoder<Decoded = <Codec as crate::Decoder<'encoded, 'decoded>>::Decoded>,
+{
+    type Decoded = <Codec as crate::Decoder<'encoded, 'decoded>>::Decoded;
+
+    fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
+        let start = *offset;
+        let decoded = self.codec.decode(encoded, offset).map_err(Self::narrowed)?;
+        self.check(&encoded[start..*offset], &decoded)?;
+        Ok(decoded)
+    }
+}
+
+// Keeps a copy of the bytes a varint decoder pulls from the reader so they can be checked afterwards.
+#[cfg(feature = "std")]
+struct Recorder<'reader, R: ?Sized> {
+    reader: &'reader mut R,
+    recorded: heapless::Vec<u8, MAX_VARINT_BYTES>,
+    overflowed: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: Read + ?Sized> Read for Recorder<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.reader.read(buf)?;
+        self.overflowed |= self.recorded.extend_from_slice(&buf[..len]).is_err();
+        Ok(len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Codec> crate::ReadDecoder for Strict<Codec>
+where
+    Codec: crate::ReadDecoder + crate::Encoder<Decoded = <Codec as crate::ReadDecoder>::Decoded>,
+{
+    type Decoded = <Codec as crate::ReadDecoder>::Decoded;
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        let mut recorder = Recorder { reader, recorded: heapless::Vec::new(), overflowed: false };
+        let decoded = self.codec.decode_from(&mut recorder).map_err(Self::narrowed)?;
+        if recorder.overflowed {
+            return Err(crate::DecodeError::NonCanonical);
+        }
+        self.check(&recorder.recorded, &decoded)?;
+        Ok(decoded)
+    }
+}
+
+impl<Codec> crate::Encoder for Strict<Codec>
+where
+    Codec: crate::Encoder,
+{
+    type Decoded = Codec::Decoded;
+
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+        let mut buffer = [0u8; MAX_VARINT_BYTES];
+        let len = self.canonical(decoded, &mut buffer)?;
+        if len > self.max_bytes {
+            return Err(crate::EncodeError::OutOfRange);
+        }
+        sink.write(&buffer[..len])
+    }
+}
 
 pub struct Option<Item> {
     pub item: Item,
@@ -375,66 +1426,80 @@ where
     type Decoded = StdOption<Item::Decoded>;
 
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_with(encoded, offset, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
         let flag = bool::decode(encoded, offset)?;
         if flag {
             Ok(StdOption::None)
         } else {
-            let item = self.item.decode(encoded, offset)?;
+            let item = self.item.decode_with(encoded, offset, budget)?;
             Ok(StdOption::Some(item))
         }
     }
 }
 
-impl<Item> crate::Encoder for Option<Item>
+#[cfg(feature = "std")]
+impl<Item> crate::ReadDecoder for Option<Item>
 where
-    Item: crate::Encoder,
-    Item::Decoded: Sized,
+    Item: crate::ReadDecoder,
 {
     type Decoded = StdOption<Item::Decoded>;
 
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
-        match decoded {
-            StdOption::None => {
-                true.encode(encoded, offset)?;
-                Ok(())
-            }
-            StdOption::Some(item) => {
-                false.encode(encoded, offset)?;
-                self.item.encode(item, encoded, offset)
-            }
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let flag = bool::decode_from(reader)?;
+        if flag {
+            Ok(StdOption::None)
+        } else {
+            let item = self.item.decode_from_with(reader, budget)?;
+            Ok(StdOption::Some(item))
         }
     }
 }
 
-impl<Item> crate::Measurer for Option<Item>
+impl<Item> crate::Encoder for Option<Item>
 where
-    Item: crate::Measurer,
+    Item: crate::Encoder,
     Item::Decoded: Sized,
 {
     type Decoded = StdOption<Item::Decoded>;
 
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
-        Ok(match decoded {
-            StdOption::None => true.measure()?,
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+        match decoded {
+            StdOption::None => {
+                true.encode(sink)?;
+                Ok(())
+            }
             StdOption::Some(item) => {
-                false.measure()?
-                + self.item.measure(item)?
+                false.encode(sink)?;
+                self.item.encode(item, sink)
             }
-        })
+        }
     }
 }
 
 
 pub struct Slice<Length> {
     pub length: Length,
+    pub max_length: usize,
 }
 
 impl<Length> Slice<Length> {
     pub const fn codec(length: Length) -> Self {
         Self {
             length,
+            max_length: usize::MAX,
         }
     }
+
+    pub fn with_max_length(self, max_length: usize) -> Self {
+        Self { max_length, ..self }
+    }
 }
 
 impl<Length> Default for Slice<Length>
@@ -455,43 +1520,49 @@ where
 
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
         let size = self.length.decode(encoded, offset)?;
-        if *offset + size > encoded.len() {
-            return Err(crate::DecodeError::InvalidData);
+        if size > self.max_length {
+            return Err(crate::DecodeError::LengthLimitExceeded);
         }
-        let buffer = &encoded[*offset..*offset + size];
-        *offset += size;
-        Ok(buffer)
+        crate::take_bytes(encoded, offset, size)
     }
 }
 
-impl<Length> crate::Encoder for Slice<Length>
+#[cfg(feature = "std")]
+impl<Length> crate::ReadDecoder for Slice<Length>
 where
-    Length: crate::Encoder<Decoded = usize>,
+    Length: crate::ReadDecoder<Decoded = usize>,
 {
-    type Decoded = [u8];
+    type Decoded = StdVec<u8>;
 
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
-        let size = decoded.len();
-        self.length.encode(&size, encoded, offset)?;
-        let end = *offset + size;
-        if end > encoded.len() {
-            return Err(crate::EncodeError::BufferTooSmall);
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let size = self.length.decode_from_with(reader, budget)?;
+        if size > self.max_length {
+            return Err(crate::DecodeError::LengthLimitExceeded);
         }
-        encoded[*offset..end].copy_from_slice(decoded);
-        *offset = end;
-        Ok(())
+        budget.charge(0, size)?;
+        let mut buffer = StdVec::new();
+        (&mut *reader).take(size as u64).read_to_end(&mut buffer)?;
+        if buffer.len() != size {
+            return Err(crate::DecodeError::Incomplete { needed: Some(size - buffer.len()) });
+        }
+        Ok(buffer)
     }
 }
 
-impl<Length> crate::Measurer for Slice<Length>
+impl<Length> crate::Encoder for Slice<Length>
 where
-    Length: crate::Measurer<Decoded = usize>,
+    Length: crate::Encoder<Decoded = usize>,
 {
     type Decoded = [u8];
 
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
         let size = decoded.len();
-        let size_measure = self.length.measure(&size)?;
-        Ok(size_measure + size)
+        self.length.encode(&size, sink)?;
+        sink.write(decoded)
     }
 }
+
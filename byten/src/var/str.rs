@@ -1,17 +1,30 @@
-use std::string::String as StdString;
-use std::ffi::CString as StdCString;
-use std::ffi::CStr as StdCStr;
+use core::ffi::CStr as StdCStr;
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, ffi::CString as StdCString, string::String as StdString};
+
+#[cfg(feature = "std")]
+use std::io::Read;
+
+#[cfg(feature = "std")]
+use crate::DecodeFrom as _;
 
 pub struct Str<Length> {
     pub length: Length,
+    pub max_length: usize,
 }
 
 impl<Length> Str<Length> {
     pub const fn codec(length: Length) -> Self {
         Self {
             length,
+            max_length: usize::MAX,
         }
     }
+
+    pub fn with_max_length(self, max_length: usize) -> Self {
+        Self { max_length, ..self }
+    }
 }
 
 impl<Length> Default for Str<Length>
@@ -32,58 +45,64 @@ where
 
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
         let size = self.length.decode(encoded, offset)?;
-        if *offset + size > encoded.len() {
-            return Err(crate::DecodeError::InvalidData);
+        if size > self.max_length {
+            return Err(crate::DecodeError::LengthLimitExceeded);
         }
-        let string_bytes = &encoded[*offset..*offset + size];
-        let string = std::str::from_utf8(string_bytes).map_err(|_| crate::DecodeError::InvalidData)?;
-        *offset += size;
-        Ok(string)
+        let string_bytes = crate::take_bytes(encoded, offset, size)?;
+        core::str::from_utf8(string_bytes).map_err(|_| crate::DecodeError::InvalidData)
     }
 }
 
-impl<Length> crate::Encoder for Str<Length>
+#[cfg(feature = "std")]
+impl<Length> crate::ReadDecoder for Str<Length>
 where
-    Length: crate::Encoder<Decoded = usize>,
+    Length: crate::ReadDecoder<Decoded = usize>,
 {
-    type Decoded = str;
+    type Decoded = StdString;
 
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
-        let size = decoded.len();
-        self.length.encode(&size, encoded, offset)?;
-        let end = *offset + size;
-        if end > encoded.len() {
-            return Err(crate::EncodeError::BufferTooSmall);
-        }
-        encoded[*offset..end].copy_from_slice(decoded.as_bytes());
-        *offset = end;
-        Ok(())
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let bytes = crate::var::Slice::codec(&self.length)
+            .with_max_length(self.max_length)
+            .decode_from_with(reader, budget)?;
+        StdString::from_utf8(bytes).map_err(|_| crate::DecodeError::InvalidData)
     }
 }
 
-impl<Length> crate::Measurer for Str<Length>
+impl<Length> crate::Encoder for Str<Length>
 where
-    Length: crate::Measurer<Decoded = usize>,
+    Length: crate::Encoder<Decoded = usize>,
 {
     type Decoded = str;
 
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
         let size = decoded.len();
-        let size_measure = self.length.measure(&size)?;
-        Ok(size_measure + size)
+        self.length.encode(&size, sink)?;
+        sink.write(decoded.as_bytes())
     }
 }
 
+#[cfg(feature = "alloc")]
 pub struct String<Length> {
     pub length: Length,
+    pub max_length: usize,
 }
 
+#[cfg(feature = "alloc")]
 impl<Length> String<Length> {
     pub const fn codec(length: Length) -> Self {
-        Self { length }
+        Self { length, max_length: usize::MAX }
+    }
+
+    pub fn with_max_length(self, max_length: usize) -> Self {
+        Self { max_length, ..self }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<Length> Default for String<Length>
 where
     Length: Default,
@@ -93,6 +112,7 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'encoded, 'length, Length> crate::Decoder<'encoded, 'static> for String<Length>
 where
     Length: crate::Decoder<'encoded, 'length, Decoded = usize>,
@@ -100,41 +120,60 @@ where
     type Decoded = StdString;
 
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
-        let str_codec = Str::codec(&self.length);
+        self.decode_with(encoded, offset, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let str_codec = Str::codec(&self.length).with_max_length(self.max_length);
         let s = str_codec.decode(encoded, offset)?;
+        budget.charge(0, s.len())?;
         Ok(s.to_owned())
     }
 }
 
-impl<Length> crate::Encoder for String<Length>
+#[cfg(feature = "std")]
+impl<Length> crate::ReadDecoder for String<Length>
 where
-    Length: crate::Encoder<Decoded = usize>,
+    Length: crate::ReadDecoder<Decoded = usize>,
 {
     type Decoded = StdString;
 
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
-        let str_codec = Str::codec(&self.length);
-        str_codec.encode(&decoded.as_str(), encoded, offset)
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        Str::codec(&self.length)
+            .with_max_length(self.max_length)
+            .decode_from_with(reader, budget)
     }
 }
 
-impl<Length> crate::Measurer for String<Length>
+#[cfg(feature = "alloc")]
+impl<Length> crate::Encoder for String<Length>
 where
-    Length: crate::Measurer<Decoded = usize>,
+    Length: crate::Encoder<Decoded = usize>,
 {
     type Decoded = StdString;
 
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
         let str_codec = Str::codec(&self.length);
-        str_codec.measure(&decoded.as_str())
+        str_codec.encode(decoded.as_str(), sink)
     }
 }
 
-pub struct CStr;
+// `max_length` counts the bytes before the NUL terminator.
+pub struct CStr {
+    pub max_length: usize,
+}
 
 impl CStr {
     pub const fn codec() -> Self {
-        Self
+        Self { max_length: usize::MAX }
+    }
+
+    pub fn with_max_length(self, max_length: usize) -> Self {
+        Self { max_length }
     }
 }
 
@@ -149,72 +188,106 @@ where
     type Decoded = &'decoded StdCStr;
 
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
-        let length = encoded[*offset..]
-            .iter()
-            .position(|&b| b == 0)
-            .ok_or(crate::DecodeError::InvalidData)?;
-        let cstr = StdCStr::from_bytes_with_nul(&encoded[*offset..*offset + length + 1])
-            .map_err(|_| crate::DecodeError::InvalidData)?;
-        *offset += length + 1;
+        let remaining = encoded.get(*offset..).unwrap_or_default();
+        // The terminator has to show up within `max_length` bytes, so no more than that is scanned.
+        let window = &remaining[..remaining.len().min(self.max_length.saturating_add(1))];
+        let cstr = match StdCStr::from_bytes_until_nul(window) {
+            Ok(cstr) => cstr,
+            Err(_) if window.len() > self.max_length => return Err(crate::DecodeError::LengthLimitExceeded),
+            Err(_) => return Err(crate::DecodeError::Incomplete { needed: None }),
+        };
+        *offset += cstr.count_bytes() + 1;
         Ok(cstr)
     }
 }
 
-impl crate::Encoder for CStr {
-    type Decoded = StdCStr;
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for CStr {
+    type Decoded = StdCString;
+
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
 
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
-        let bytes = decoded.to_bytes_with_nul();
-        let end = *offset + bytes.len();
-        if end > encoded.len() {
-            return Err(crate::EncodeError::BufferTooSmall);
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let mut bytes = alloc::vec::Vec::new();
+        loop {
+            let byte = u8::decode_from(reader)?;
+            if byte == 0 {
+                break;
+            }
+            if bytes.len() == self.max_length {
+                return Err(crate::DecodeError::LengthLimitExceeded);
+            }
+            budget.charge(0, 1)?;
+            bytes.push(byte);
         }
-        encoded[*offset..end].copy_from_slice(bytes);
-        *offset = end;
-        Ok(())
+        StdCString::new(bytes).map_err(|_| crate::DecodeError::InvalidData)
     }
 }
 
-impl crate::Measurer for CStr {
+impl crate::Encoder for CStr {
     type Decoded = StdCStr;
 
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
-        Ok(decoded.to_bytes_with_nul().len())
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+        sink.write(decoded.to_bytes_with_nul())
     }
 }
 
-pub struct CString;
+#[cfg(feature = "alloc")]
+pub struct CString {
+    pub max_length: usize,
+}
 
+#[cfg(feature = "alloc")]
 impl CString {
     pub const fn codec() -> Self {
-        Self
+        Self { max_length: usize::MAX }
+    }
+
+    pub fn with_max_length(self, max_length: usize) -> Self {
+        Self { max_length }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Default for CString {
     fn default() -> Self { Self::codec() }
 }
 
+#[cfg(feature = "alloc")]
 impl<'encoded> crate::Decoder<'encoded, 'static> for CString {
     type Decoded = StdCString;
 
     fn decode(&self, encoded: &'encoded [u8], offset: &mut usize) -> Result<Self::Decoded, crate::DecodeError> {
-        Ok(CStr::codec().decode(encoded, offset)?.to_owned())
+        self.decode_with(encoded, offset, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_with(&self, encoded: &'encoded [u8], offset: &mut usize, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        let cstr = CStr::codec().with_max_length(self.max_length).decode(encoded, offset)?;
+        budget.charge(0, cstr.count_bytes() + 1)?;
+        Ok(cstr.to_owned())
     }
 }
 
-impl crate::Encoder for CString {
+#[cfg(feature = "std")]
+impl crate::ReadDecoder for CString {
     type Decoded = StdCString;
 
-    fn encode(&self, decoded: &Self::Decoded, encoded: &mut [u8], offset: &mut usize) -> Result<(), crate::EncodeError> {
-        CStr::codec().encode(&decoded.as_c_str(), encoded, offset)
+    fn decode_from<R: Read + ?Sized>(&self, reader: &mut R) -> Result<Self::Decoded, crate::DecodeError> {
+        self.decode_from_with(reader, &mut crate::limit::Budget::new())
+    }
+
+    fn decode_from_with<R: Read + ?Sized>(&self, reader: &mut R, budget: &mut crate::limit::Budget) -> Result<Self::Decoded, crate::DecodeError> {
+        CStr::codec().with_max_length(self.max_length).decode_from_with(reader, budget)
     }
 }
 
-impl crate::Measurer for CString {
+#[cfg(feature = "alloc")]
+impl crate::Encoder for CString {
     type Decoded = StdCString;
 
-    fn measure(&self, decoded: &Self::Decoded) -> Result<usize, crate::EncodeError> {
-        CStr::codec().measure(&decoded.as_c_str())
+    fn encode<S: crate::Sink + ?Sized>(&self, decoded: &Self::Decoded, sink: &mut S) -> Result<(), crate::EncodeError> {
+        CStr::codec().encode(decoded.as_c_str(), sink)
     }
 }
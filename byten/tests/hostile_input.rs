@@ -0,0 +1,176 @@
+#![cfg(feature = "std")]
+
+use std::{io::Read, panic::{self, AssertUnwindSafe}};
+
+use byten::{
+    Decode, DecodeFrom, Decoder, ReadDecoder, SelfCodec, fix, prim, util::{self, Convert, Owned}, var,
+};
+
+const ITERATIONS: usize = 2000;
+
+// Mixes uniform noise with runs of 0x00, 0x80 and 0xff, which reach huge lengths, long varints and missing terminators.
+fn hostile_input(rng: &mut fastrand::Rng) -> Vec<u8> {
+    let len = rng.usize(0..64);
+    match rng.u8(0..4) {
+        0 => (0..len).map(|_| rng.u8(..)).collect(),
+        1 => (0..len).map(|_| if rng.bool() { 0xff } else { rng.u8(..) }).collect(),
+        2 => (0..len).map(|_| if rng.bool() { 0x80 } else { 0x00 }).collect(),
+        _ => {
+            let mut input = vec![0xff; rng.usize(0..12)];
+            input.extend((0..len).map(|_| rng.u8(..)));
+            input
+        },
+    }
+}
+
+fn hostile_offset(rng: &mut fastrand::Rng, input: &[u8]) -> usize {
+    match rng.u8(0..8) {
+        0 => usize::MAX - rng.usize(0..16),
+        1 => input.len() + rng.usize(0..16),
+        _ => rng.usize(0..=input.len()),
+    }
+}
+
+fn check(name: &str, decode: impl Fn(&[u8], &mut usize) -> bool, read: impl Fn(&mut &[u8])) {
+    let mut rng = fastrand::Rng::with_seed(0xb7e0);
+    for _ in 0..ITERATIONS {
+        let input = hostile_input(&mut rng);
+        let start = hostile_offset(&mut rng, &input);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut offset = start;
+            if decode(&input, &mut offset) {
+                assert!(offset <= input.len(), "offset {} past the end", offset);
+            }
+            read(&mut input.as_slice());
+        }));
+        assert!(result.is_ok(), "{} panicked at offset {} on {:?}", name, start, input);
+    }
+}
+
+macro_rules! check_decoder {
+    ($name:expr, $codec:expr) => {
+        check(
+            $name,
+            |input: &[u8], offset: &mut usize| Decoder::decode(&$codec, input, offset).is_ok(),
+            |reader: &mut &[u8]| { let _ = ReadDecoder::decode_from(&$codec, reader); },
+        )
+    };
+}
+
+macro_rules! check_self {
+    ($ty:ty) => {
+        check(
+            stringify!($ty),
+            |input: &[u8], offset: &mut usize| <$ty as Decode>::decode(input, offset).is_ok(),
+            |reader: &mut &[u8]| { let _ = <$ty as DecodeFrom>::decode_from(reader); },
+        )
+    };
+}
+
+fn check_reader<C: ReadDecoder>(name: &str, codec: C) {
+    let mut rng = fastrand::Rng::with_seed(0x5eed);
+    for _ in 0..ITERATIONS {
+        let input = hostile_input(&mut rng);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut reader = input.as_slice().chain(&[][..]);
+            let _ = codec.decode_from(&mut reader);
+        }));
+        assert!(result.is_ok(), "{} panicked on {:?}", name, input);
+    }
+}
+
+#[test]
+fn test_self_codecs() {
+    check_self!(u8);
+    check_self!(bool);
+    check_self!(i8);
+    check_self!(char);
+    check_self!(std::num::NonZeroU8);
+    check_self!(std::num::Wrapping<i8>);
+    check_self!([u8; 7]);
+    check_self!([bool; 5]);
+    check_self!([char; 3]);
+    check_self!(Box<u8>);
+    check_self!(std::ffi::CString);
+}
+
+#[test]
+fn test_prim_codecs() {
+    check_decoder!("U16BE", prim::U16BE);
+    check_decoder!("U16LE", prim::U16LE);
+    check_decoder!("I16BE", prim::I16BE);
+    check_decoder!("I16LE", prim::I16LE);
+    check_decoder!("U32BE", prim::U32BE);
+    check_decoder!("U32LE", prim::U32LE);
+    check_decoder!("I32BE", prim::I32BE);
+    check_decoder!("I32LE", prim::I32LE);
+    check_decoder!("U64BE", prim::U64BE);
+    check_decoder!("U64LE", prim::U64LE);
+    check_decoder!("I64BE", prim::I64BE);
+    check_decoder!("I64LE", prim::I64LE);
+    check_decoder!("U128BE", prim::U128BE);
+    check_decoder!("U128LE", prim::U128LE);
+    check_decoder!("I128BE", prim::I128BE);
+    check_decoder!("I128LE", prim::I128LE);
+    check_decoder!("F32BE", prim::F32BE);
+    check_decoder!("F32LE", prim::F32LE);
+    check_decoder!("F64BE", prim::F64BE);
+    check_decoder!("F64LE", prim::F64LE);
+    check_decoder!("CanonicalNaN", prim::CanonicalNaN::<prim::F64LE>::default());
+    check_decoder!("U24BE", prim::U24BE);
+    check_decoder!("I24LE", prim::I24LE);
+    check_decoder!("U40LE", prim::U40LE);
+    check_decoder!("I48BE", prim::I48BE);
+    check_decoder!("U56BE", prim::U56BE);
+    check_decoder!("I56LE", prim::I56LE);
+    check_decoder!("U32Dyn", prim::U32Dyn::codec(prim::ByteOrder::Little));
+    check_decoder!("I24Dyn", prim::I24Dyn::codec(prim::ByteOrder::Big));
+    check_decoder!("CharBE", prim::CharBE);
+}
+
+#[test]
+fn test_var_codecs() {
+    check_decoder!("var::U64BE", var::U64BE);
+    check_decoder!("var::U32BE", var::U32BE);
+    check_decoder!("var::U16BE", var::U16BE);
+    check_decoder!("var::USizeBE", var::USizeBE);
+    check_decoder!("var::U64LE", var::U64LE);
+    check_decoder!("var::U32LE", var::U32LE);
+    check_decoder!("var::USizeLE", var::USizeLE);
+    check_decoder!("var::I64BE", var::I64BE);
+    check_decoder!("var::I16BE", var::I16BE);
+    check_decoder!("var::I64LE", var::I64LE);
+    check_decoder!("var::I16LE", var::I16LE);
+    check_decoder!("var::I64ZigZagBE", var::I64ZigZagBE);
+    check_decoder!("var::I32ZigZagLE", var::I32ZigZagLE);
+    check_decoder!("var::U128BE", var::U128BE);
+    check_decoder!("var::U128LE", var::U128LE);
+    check_decoder!("var::I128BE", var::I128BE);
+    check_decoder!("var::I128LE", var::I128LE);
+    check_decoder!("var::I128ZigZagBE", var::I128ZigZagBE);
+    check_decoder!("var::Strict<U64BE>", var::Strict::codec(var::U64BE));
+    check_decoder!("var::Strict<I64LE>", var::Strict::codec(var::I64LE).with_max_bytes(4));
+    check_decoder!("var::Remaining", var::Remaining);
+    check_decoder!("var::Slice", var::Slice::<var::USizeBE>::default());
+    check_decoder!("var::Slice<U64BE>", var::Slice::<Convert<prim::U64BE, usize>>::default());
+    check_decoder!("var::Option", var::Option::<prim::U32LE>::default());
+    check_decoder!("var::Vec", var::Vec::<var::USizeBE, prim::U16BE>::default());
+    check_decoder!("var::Vec<var::Vec>", var::Vec::<var::USizeBE, var::Vec<var::USizeBE, SelfCodec<u8>>>::default());
+    check_decoder!("var::Vec<Str>", var::Vec::<var::USizeBE, var::str::Str<var::USizeBE>>::default());
+    check_decoder!("var::str::Str", var::str::Str::<var::USizeBE>::default());
+    check_decoder!("var::str::String", var::str::String::<var::USizeBE>::default());
+    check_decoder!("var::str::CStr", var::str::CStr::codec());
+    check_decoder!("var::str::CString", var::str::CString::codec());
+}
+
+#[test]
+fn test_fix_and_util_codecs() {
+    check_decoder!("fix::Array", fix::Array::<prim::U32BE, 5>::default());
+    check_decoder!("fix::Array<Str>", fix::Array::<var::str::Str<var::USizeBE>, 3>::default());
+    check_decoder!("fix::Magic", fix::Magic::codec(&[0xff; 40]));
+    check_decoder!("fix::Const", fix::Const::codec(var::U32BE, 0x80));
+    check_decoder!("util::Convert", Convert::<prim::U64BE, u8>::default());
+    check_decoder!("util::NonZero", util::NonZero::codec(prim::U32BE));
+    check_decoder!("util::Wrapping", util::Wrapping::codec(var::I64LE));
+    check_reader("util::Owned", Owned::<var::str::Str<var::USizeBE>, String>::default());
+}
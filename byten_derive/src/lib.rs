@@ -1,142 +1,164 @@
 use proc_macro::TokenStream;
-use syn::DeriveInput;
+use syn::{DeriveInput, parse_macro_input};
 use quote::quote;
 
 mod schema;
 use schema::*;
 
+// `DecodeOwned` itself is blanket-implemented for every type decoding from any lifetime, which the
+// `Decode` impl emitted here is unless the type or its bounds borrow from the input.
 #[proc_macro_derive(DecodeOwned, attributes(byten))]
 pub fn derive_decode_owned(input: TokenStream) -> TokenStream {
-    let input: DeriveInput = syn::parse(input).unwrap();
-    let ident = &input.ident;
-    let generics = &input.generics;
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_decode_owned(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
 
-    let schema = match &input.data {
-        syn::Data::Struct(_) => interpret_struct_schema(&input),
-        syn::Data::Enum(_) => interpret_enum_schema(&input),
-        _ => panic!("DecodeOwned can only be derived for structs and enums"),
-    };
+fn expand_decode_owned(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let schema = interpret_schema(input, "DecodeOwned")?;
+    derive_decode_impl(input, schema.as_ref())
+}
 
+fn derive_decode_impl(input: &DeriveInput, schema: &dyn BinarySchema) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let generics = impl_generics(input, DerivedTrait::Decode)?;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let lifetime = encoded_lifetime();
+
+    let encoded = internal_ident("encoded");
+    let offset = internal_ident("offset");
+    let budget = internal_ident("budget");
     let decoded = schema.decode(&DecodeContext {
-        encoded: quote! { encoded },
-        offset: quote! { offset },
+        wrapper: quote! { Self },
+        encoded: quote! { #encoded },
+        offset: quote! { #offset },
+        budget: quote! { #budget },
+        location: quote! {},
     });
 
-    quote! {
-        impl #generics ::byten::Decode<'_> for #ident #generics {
-            fn decode(encoded: &'_ [u8], offset: &mut usize) -> Result<Self, ::byten::DecodeError> {
+    Ok(quote! {
+        impl #impl_generics ::byten::Decode<#lifetime> for #ident #ty_generics #where_clause {
+            fn decode(#encoded: &#lifetime [u8], #offset: &mut usize) -> Result<Self, ::byten::DecodeError> {
+                Self::decode_with(#encoded, #offset, &mut ::byten::limit::Budget::new())
+            }
+
+            fn decode_with(#encoded: &#lifetime [u8], #offset: &mut usize, #budget: &mut ::byten::limit::Budget) -> Result<Self, ::byten::DecodeError> {
                 Ok(#decoded)
             }
         }
-    }.into()
+    })
 }
 
-#[proc_macro_derive(Decode, attributes(byten))]
-pub fn derive_decode(input: TokenStream) -> TokenStream {
-    let input: DeriveInput = syn::parse(input).unwrap();
-    let ident = &input.ident;
-    let generics = &input.generics;
+// Opt-in, since reading needs every field codec to implement `ReadDecoder` as well as `Decoder`.
+#[proc_macro_derive(DecodeFrom, attributes(byten))]
+pub fn derive_decode_from(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_decode_from(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
 
-    let schema = match &input.data {
-        syn::Data::Struct(_) => interpret_struct_schema(&input),
-        syn::Data::Enum(_) => interpret_enum_schema(&input),
-        _ => panic!("Decode can only be derived for structs and enums"),
-    };
+// `DecodeFrom` only exists when byten is built with `std`, which forwards the feature here.
+fn expand_decode_from(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    if !cfg!(feature = "std") {
+        return Err(syn::Error::new_spanned(&input.ident, "`DecodeFrom` requires byten's `std` feature"));
+    }
 
-    let decoded = schema.decode(&DecodeContext {
-        encoded: quote! { encoded },
-        offset: quote! { offset },
+    let schema = interpret_schema(input, "DecodeFrom")?;
+
+    let ident = &input.ident;
+    let generics = impl_generics(input, DerivedTrait::DecodeFrom)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let reader = internal_ident("reader");
+    let budget = internal_ident("budget");
+    let read = schema.decode_from(&ReadContext {
+        wrapper: quote! { Self },
+        reader: quote! { #reader },
+        budget: quote! { #budget },
+        location: quote! {},
     });
 
-    quote! {
-        impl #generics ::byten::Decode<'encoded> for #ident #generics {
-            fn decode(encoded: &'encoded [u8], offset: &mut usize) -> Result<Self, ::byten::DecodeError> {
-                Ok(#decoded)
+    Ok(quote! {
+        impl #impl_generics ::byten::DecodeFrom for #ident #ty_generics #where_clause {
+            fn decode_from<R: ::std::io::Read + ?Sized>(#reader: &mut R) -> Result<Self, ::byten::DecodeError> {
+                Self::decode_from_with(#reader, &mut ::byten::limit::Budget::new())
+            }
+
+            fn decode_from_with<R: ::std::io::Read + ?Sized>(#reader: &mut R, #budget: &mut ::byten::limit::Budget) -> Result<Self, ::byten::DecodeError> {
+                Ok(#read)
             }
         }
-    }.into()
+    })
+}
+
+#[proc_macro_derive(Decode, attributes(byten))]
+pub fn derive_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_decode(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand_decode(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let schema = interpret_schema(input, "Decode")?;
+    derive_decode_impl(input, schema.as_ref())
 }
 
 #[proc_macro_derive(Encode, attributes(byten))]
 pub fn derive_encode(input: TokenStream) -> TokenStream {
-    let input: DeriveInput = syn::parse(input).unwrap();
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_encode(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand_encode(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let ident = &input.ident;
-    let generics = &input.generics;
+    let generics = impl_generics(input, DerivedTrait::Encode)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let schema = match &input.data {
-        syn::Data::Struct(_) => interpret_struct_schema(&input),
-        syn::Data::Enum(_) => interpret_enum_schema(&input),
-        _ => panic!("Encode can only be derived for structs and enums"),
-    };
+    let schema = interpret_schema(input, "Encode")?;
 
+    let sink = internal_ident("sink");
     let encoded = schema.encode(&EncodeContext {
         wrapper: quote! { Self },
         decoded: quote! { self },
-        encoded: quote! { encoded },
-        offset: quote! { offset },
+        sink: quote! { #sink },
     });
 
-    quote! {
-        impl #generics ::byten::Encode for #ident #generics {
-            fn encode(&self, encoded: &mut [u8], offset: &mut usize) -> Result<(), ::byten::EncodeError> {
+    Ok(quote! {
+        impl #impl_generics ::byten::Encode for #ident #ty_generics #where_clause {
+            fn encode<S: ::byten::Sink + ?Sized>(&self, #sink: &mut S) -> Result<(), ::byten::EncodeError> {
                 #encoded
                 Ok(())
             }
         }
-    }.into()
+    })
 }
 
 #[proc_macro_derive(MeasureFixed, attributes(byten))]
 pub fn derive_measure_fixed(input: TokenStream) -> TokenStream {
-    let input: DeriveInput = syn::parse(input).unwrap();
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_measure_fixed(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand_measure_fixed(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let ident = &input.ident;
-    let generics = &input.generics;
+    let generics = impl_generics(input, DerivedTrait::MeasureFixed)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let schema = match &input.data {
-        syn::Data::Struct(_) => interpret_struct_schema(&input),
-        syn::Data::Enum(_) => interpret_enum_schema(&input),
-        _ => panic!("MeasureFixed can only be derived for structs and enums"),
-    };
+    let schema = interpret_schema(input, "MeasureFixed")?;
 
-    let measured = schema.measure_fixed();
+    let measured = schema.measure_fixed()?;
 
-    quote! {
-        impl #generics ::byten::MeasureFixed for #ident #generics {
+    Ok(quote! {
+        impl #impl_generics ::byten::MeasureFixed for #ident #ty_generics #where_clause {
             fn measure_fixed() -> usize {
                 #measured
             }
         }
-
-        impl #generics ::byten::Measure for #ident #generics {
-            fn measure(&self) -> Result<usize, ::byten::EncodeError> {
-                Ok(Self::measure_fixed())
-            }
-        }
-    }.into()
+    })
 }
 
+// `Measure` is implemented for every `Encode` type by running the encoder against a
+// counting sink, so this derive has nothing left to generate and only remains for old derive lists.
+#[deprecated(note = "`Measure` is implemented for every `Encode` type; drop the derive")]
 #[proc_macro_derive(Measure, attributes(byten))]
-pub fn derive_measure(input: TokenStream) -> TokenStream {
-    let input: DeriveInput = syn::parse(input).unwrap();
-    let ident = &input.ident;
-    let generics = &input.generics;
-
-    let schema = match &input.data {
-        syn::Data::Struct(_) => interpret_struct_schema(&input),
-        syn::Data::Enum(_) => interpret_enum_schema(&input),
-        _ => panic!("Measure can only be derived for structs and enums"),
-    };
-
-    let measured = schema.measure(&MeasureContext {
-        wrapper: quote! { Self },
-        decoded: quote! { self },
-    });
-
-    quote! {
-        impl #generics ::byten::Measure for #ident #generics {
-            fn measure(&self) -> Result<usize, ::byten::EncodeError> {
-                Ok(#measured)
-            }
-        }
-    }.into()
+pub fn derive_measure(_input: TokenStream) -> TokenStream {
+    TokenStream::new()
 }
@@ -0,0 +1,117 @@
+use quote::quote;
+use syn::{Attribute, Error, Expr, ExprLit, Lit, LitStr, Meta, Token, WherePredicate, punctuated::Punctuated};
+
+// Everything a set of `#[byten(...)]` attributes can carry: a bare codec expression and `key = value` options.
+#[derive(Default)]
+pub struct BytenAttributes {
+    pub codec: Option<Expr>,
+    pub max_depth: Option<Expr>,
+    // The `prim::Endian` marker selected by `endian = "..."`.
+    pub endian: Option<proc_macro2::TokenStream>,
+    pub bounds: Bounds,
+    // The value of a `skip` field on decode, `Default::default()` unless given as `skip = ...`.
+    pub skip: Option<Expr>,
+    // Signature bytes written before the container or field by `magic = b"..."`.
+    pub magic: Option<Expr>,
+}
+
+pub type Predicates = Punctuated<WherePredicate, Token![,]>;
+
+// `where` predicates that replace the inferred ones, from `bound = "..."` for every derived trait
+// or `bound(decode = "...", decode_from = "...", encode = "...", measure_fixed = "...")` per trait.
+#[derive(Default)]
+pub struct Bounds {
+    pub all: Option<Predicates>,
+    pub decode: Option<Predicates>,
+    pub decode_from: Option<Predicates>,
+    pub encode: Option<Predicates>,
+    pub measure_fixed: Option<Predicates>,
+}
+
+fn parse_predicates(value: &Expr) -> syn::Result<Predicates> {
+    let Expr::Lit(ExprLit { lit: Lit::Str(literal), .. }) = value else {
+        return Err(Error::new_spanned(value, "byten bound must be a string literal of where predicates"));
+    };
+    LitStr::parse_with(literal, Predicates::parse_terminated)
+}
+
+fn parse_bounds(args: &Punctuated<Expr, Token![,]>, bounds: &mut Bounds) -> syn::Result<()> {
+    for arg in args {
+        let Expr::Assign(assign) = arg else {
+            return Err(Error::new_spanned(arg, "expected `<trait> = \"<where predicates>\"`"));
+        };
+        let key = match assign.left.as_ref() {
+            Expr::Path(key) => key.path.get_ident(),
+            _ => None,
+        };
+        let slot = match key.map(ToString::to_string).as_deref() {
+            Some("decode") => &mut bounds.decode,
+            Some("decode_from") => &mut bounds.decode_from,
+            Some("encode") => &mut bounds.encode,
+            Some("measure_fixed") => &mut bounds.measure_fixed,
+            _ => return Err(Error::new_spanned(&assign.left, "unknown byten bound, expected `decode`, `decode_from`, `encode` or `measure_fixed`")),
+        };
+        *slot = Some(parse_predicates(&assign.right)?);
+    }
+    Ok(())
+}
+
+fn parse_endian(endian: &Expr) -> syn::Result<proc_macro2::TokenStream> {
+    let Expr::Lit(ExprLit { lit: Lit::Str(literal), .. }) = endian else {
+        return Err(Error::new_spanned(endian, "byten endian must be a string literal"));
+    };
+    match literal.value().as_str() {
+        "be" | "big" => Ok(quote! { ::byten::prim::BigEndian }),
+        "le" | "little" => Ok(quote! { ::byten::prim::LittleEndian }),
+        "ne" | "native" => Ok(quote! { ::byten::prim::NativeEndian }),
+        _ => Err(Error::new_spanned(literal, "byten endian must be one of \"be\", \"le\" or \"native\"")),
+    }
+}
+
+pub fn parse_byten_attributes(attrs: &[Attribute]) -> syn::Result<BytenAttributes> {
+    let mut parsed = BytenAttributes::default();
+    for attribute in attrs {
+        if !attribute.path().is_ident("byten") {
+            continue;
+        }
+        let Meta::List(meta) = &attribute.meta else {
+            return Err(Error::new_spanned(attribute, "expected `#[byten(...)]`"));
+        };
+        let items = meta.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+        for item in items {
+            match item {
+                Expr::Assign(assign) => {
+                    let key = match assign.left.as_ref() {
+                        Expr::Path(key) => key.path.get_ident(),
+                        _ => None,
+                    };
+                    match key.map(ToString::to_string).as_deref() {
+                        // Depth is tracked in thread-local state, which byten only has with `std`.
+                        Some("max_depth") if !cfg!(feature = "std") => {
+                            return Err(Error::new_spanned(assign, "`max_depth` requires byten's `std` feature"));
+                        },
+                        Some("max_depth") => parsed.max_depth = Some(*assign.right),
+                        Some("endian") => parsed.endian = Some(parse_endian(&assign.right)?),
+                        Some("bound") => parsed.bounds.all = Some(parse_predicates(&assign.right)?),
+                        Some("skip") => parsed.skip = Some(*assign.right),
+                        Some("magic") => parsed.magic = Some(*assign.right),
+                        _ => return Err(Error::new_spanned(&assign.left, "unknown byten attribute key, expected `max_depth`, `endian`, `bound`, `skip` or `magic`")),
+                    }
+                },
+                Expr::Path(path) if path.path.is_ident("skip") => {
+                    parsed.skip = Some(syn::parse_quote! { ::core::default::Default::default() });
+                },
+                Expr::Call(call) if matches!(call.func.as_ref(), Expr::Path(func) if func.path.is_ident("bound")) => {
+                    parse_bounds(&call.args, &mut parsed.bounds)?;
+                },
+                codec => {
+                    if parsed.codec.is_some() {
+                        return Err(Error::new_spanned(codec, "duplicate byten codec"));
+                    }
+                    parsed.codec = Some(codec);
+                },
+            }
+        }
+    }
+    Ok(parsed)
+}
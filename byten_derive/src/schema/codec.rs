@@ -1,7 +1,7 @@
 use quote::quote;
-use syn::{Attribute, Expr, Meta};
+use syn::{Error, Expr, Field, Type};
 
-use super::{BinarySchema, DecodeContext, EncodeContext, MeasureContext};
+use super::{BinarySchema, DecodeContext, EncodeContext, ReadContext, internal_ident, parse_byten_attributes};
 
 pub fn interpret_codec_schema(expr: &Expr) -> Box<dyn BinarySchema> {
     Box::new(CodecSchema {
@@ -18,39 +18,148 @@ impl BinarySchema for CodecSchema {
         let expr = &self.expr;
         let encoded = &ctx.encoded;
         let offset = &ctx.offset;
-        quote! { ::byten::Decoder::decode(&#expr, #encoded, #offset)? }
+        let budget = &ctx.budget;
+        let location = &ctx.location;
+        if location.is_empty() {
+            return quote! { ::byten::Decoder::decode_with(&#expr, #encoded, #offset, #budget)? };
+        }
+        let start = internal_ident("start");
+        quote! { {
+            let #start = *#offset;
+            ::byten::Decoder::decode_with(&#expr, #encoded, #offset, #budget)
+                .map_err(|error| error.at(#start) #location)?
+        } }
+    }
+
+    fn decode_from(&self, ctx: &ReadContext) -> proc_macro2::TokenStream {
+        let expr = &self.expr;
+        let reader = &ctx.reader;
+        let budget = &ctx.budget;
+        let location = &ctx.location;
+        if location.is_empty() {
+            return quote! { ::byten::ReadDecoder::decode_from_with(&#expr, #reader, #budget)? };
+        }
+        quote! {
+            ::byten::ReadDecoder::decode_from_with(&#expr, #reader, #budget)
+                .map_err(|error| error #location)?
+        }
     }
 
     fn encode(&self, ctx: &EncodeContext) -> proc_macro2::TokenStream {
         let expr = &self.expr;
         let decoded = &ctx.decoded;
-        let encoded = &ctx.encoded;
-        let offset = &ctx.offset;
-        quote! { ::byten::Encoder::encode(&#expr, #decoded, #encoded, #offset)? }
+        let sink = &ctx.sink;
+        quote! { ::byten::Encoder::encode(&#expr, #decoded, #sink)? }
     }
 
-    fn measure_fixed(&self) -> proc_macro2::TokenStream {
+    fn measure_fixed(&self) -> syn::Result<proc_macro2::TokenStream> {
         let expr = &self.expr;
-        quote! { ::byten::FixedMeasurer::measure_fixed(&#expr) }
+        Ok(quote! { ::byten::FixedMeasurer::measure_fixed(&#expr) })
     }
+}
 
-    fn measure(&self, ctx: &MeasureContext) -> proc_macro2::TokenStream {
-        let expr = &self.expr;
+// A field that is not encoded and takes `Default::default()` or the `skip = ...` expression on decode.
+struct SkipSchema {
+    value: Expr,
+}
+
+impl BinarySchema for SkipSchema {
+    fn decode(&self, _ctx: &DecodeContext) -> proc_macro2::TokenStream {
+        let value = &self.value;
+        quote! { #value }
+    }
+
+    fn decode_from(&self, _ctx: &ReadContext) -> proc_macro2::TokenStream {
+        let value = &self.value;
+        quote! { #value }
+    }
+
+    fn encode(&self, ctx: &EncodeContext) -> proc_macro2::TokenStream {
         let decoded = &ctx.decoded;
-        quote! { ::byten::Measurer::measure(&#expr, #decoded)? }
+        quote! { { let _ = #decoded; } }
+    }
+
+    fn measure_fixed(&self) -> syn::Result<proc_macro2::TokenStream> {
+        Ok(quote! { 0 })
     }
 }
 
-pub fn parse_byten_attribute(attr: &Vec<Attribute>) -> Option<Expr> {
-    for attribute in attr {
-        if attribute.path().is_ident("byten") {
-            match &attribute.meta {
-                Meta::List(meta) => {
-                    return Some(meta.parse_args::<Expr>().expect("Invalid byten attribute"));
-                },
-                _ => panic!("Invalid byten attribute format"),
-            }
+// `fix::Magic` bytes checked before whatever `inner` decodes.
+struct MagicSchema {
+    magic: Box<dyn BinarySchema>,
+    inner: Box<dyn BinarySchema>,
+}
+
+pub fn interpret_magic_schema(magic: Option<Expr>, inner: Box<dyn BinarySchema>) -> Box<dyn BinarySchema> {
+    let Some(magic) = magic else {
+        return inner;
+    };
+    Box::new(MagicSchema {
+        magic: interpret_codec_schema(&syn::parse_quote! { ::byten::fix::Magic::codec(#magic) }),
+        inner,
+    })
+}
+
+impl BinarySchema for MagicSchema {
+    fn decode(&self, ctx: &DecodeContext) -> proc_macro2::TokenStream {
+        let magic = self.magic.decode(ctx);
+        let inner = self.inner.decode(ctx);
+        quote! { { #magic; #inner } }
+    }
+
+    fn decode_from(&self, ctx: &ReadContext) -> proc_macro2::TokenStream {
+        let magic = self.magic.decode_from(ctx);
+        let inner = self.inner.decode_from(ctx);
+        quote! { { #magic; #inner } }
+    }
+
+    fn encode(&self, ctx: &EncodeContext) -> proc_macro2::TokenStream {
+        let magic = self.magic.encode(&EncodeContext {
+            wrapper: quote! {},
+            decoded: quote! { &() },
+            sink: ctx.sink.clone(),
+        });
+        let inner = self.inner.encode(ctx);
+        quote! { #magic; #inner }
+    }
+
+    fn measure_fixed(&self) -> syn::Result<proc_macro2::TokenStream> {
+        let magic = self.magic.measure_fixed()?;
+        let inner = self.inner.measure_fixed()?;
+        Ok(quote! { (#magic) + (#inner) })
+    }
+}
+
+pub fn interpret_field_codec_schema(field: &Field, endian: Option<&proc_macro2::TokenStream>) -> syn::Result<Box<dyn BinarySchema>> {
+    let attributes = parse_byten_attributes(&field.attrs)?;
+    let schema: Box<dyn BinarySchema> = match (attributes.skip, attributes.codec) {
+        (Some(_), Some(codec)) => return Err(Error::new_spanned(codec, "skipped field cannot have a byten codec")),
+        (Some(value), None) => Box::new(SkipSchema { value }),
+        (None, codec) => interpret_codec_schema(&codec.unwrap_or_else(|| default_codec(&field.ty, endian))),
+    };
+    Ok(interpret_magic_schema(attributes.magic, schema))
+}
+
+// The codec used for a field without `#[byten(...)]`: bare multi-byte primitives follow the
+// container's `endian`, everything else encodes itself.
+pub fn default_codec(ty: &Type, endian: Option<&proc_macro2::TokenStream>) -> Expr {
+    if let (Some(endian), Type::Path(path)) = (endian, ty) {
+        let prim = path.path.get_ident().and_then(|ident| match ident.to_string().as_str() {
+            "u16" => Some(quote! { U16 }),
+            "u32" => Some(quote! { U32 }),
+            "u64" => Some(quote! { U64 }),
+            "u128" => Some(quote! { U128 }),
+            "i16" => Some(quote! { I16 }),
+            "i32" => Some(quote! { I32 }),
+            "i64" => Some(quote! { I64 }),
+            "i128" => Some(quote! { I128 }),
+            "f32" => Some(quote! { F32 }),
+            "f64" => Some(quote! { F64 }),
+            _ => None,
+        });
+        if let Some(prim) = prim {
+            return syn::parse_quote! { ::byten::prim::#prim::<#endian>::codec() };
         }
     }
-    None
+    syn::parse_quote! { ::byten::SelfCodec::<#ty>::default() }
 }
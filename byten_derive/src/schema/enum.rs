@@ -1,126 +1,148 @@
-use syn::{Data, DeriveInput, Expr, Ident, Meta, TypePath};
+use syn::{DataEnum, DeriveInput, Error, Expr, Ident, Meta, Type, TypePath};
 use quote::quote;
 
-use super::{BinarySchema, DecodeContext, EncodeContext, FieldsSchema, MeasureContext, interpret_codec_schema, parse_byten_attribute, interpret_fields_schema};
+use super::{BinarySchema, DecodeContext, EncodeContext, FieldsSchema, ReadContext, default_codec, depth_key, internal_ident, interpret_codec_schema, interpret_magic_schema, parse_byten_attributes, interpret_fields_schema};
 
-pub fn interpret_enum_schema(input: &DeriveInput) -> Box<dyn BinarySchema> {
-    let Data::Enum(ref data) = input.data else {
-        panic!("EnumSchema can only be created from enum data");
-    };
-
-    let repr = input.attrs.iter().find(
-        |attr| attr.path().is_ident("repr")
-    ).expect("Enum must have a repr attribute");
+pub fn interpret_enum_schema(input: &DeriveInput, data: &DataEnum) -> syn::Result<Box<dyn BinarySchema>> {
+    let repr = input.attrs.iter()
+        .find(|attr| attr.path().is_ident("repr"))
+        .ok_or_else(|| Error::new_spanned(&input.ident, "enum must have a `#[repr(...)]` attribute naming its discriminant type"))?;
     let repr = match &repr.meta {
-        Meta::List(meta) => {
-            meta.parse_args::<TypePath>().expect("Invalid repr attribute")
-        },
-        _ => panic!("Invalid repr attribute format"),
+        Meta::List(meta) => meta.parse_args::<TypePath>()?,
+        _ => return Err(Error::new_spanned(repr, "expected `#[repr(<integer type>)]`")),
     };
 
-    let discriminator_codec_path = parse_byten_attribute(&input.attrs).unwrap_or_else(|| syn::parse_quote!{
-        ::byten::SelfCodec::<#repr>::default()
+    let attributes = parse_byten_attributes(&input.attrs)?;
+    let endian = attributes.endian;
+    let discriminator_codec_path = attributes.codec.unwrap_or_else(|| {
+        default_codec(&Type::Path(repr), endian.as_ref())
     });
-    let discriminator = interpret_codec_schema(&discriminator_codec_path);
+    // A container `magic` precedes the discriminant, so it is checked along with it.
+    let discriminator = interpret_magic_schema(attributes.magic, interpret_codec_schema(&discriminator_codec_path));
 
     let variants = data.variants.iter().map(|variant| {
         let ident = variant.ident.clone();
-        let schema = interpret_fields_schema(&variant.fields);
+        let schema = interpret_fields_schema(&variant.fields, endian.as_ref())?;
         let discriminant = match &variant.discriminant {
             Some((_, expr)) => expr.clone(),
-            None => panic!("Enum variants must have discriminants"),
+            None => return Err(Error::new_spanned(variant, "enum variant must have an explicit discriminant")),
         };
-        (ident, schema, discriminant)
-    }).collect();
-    Box::new(EnumSchema {
+        Ok((ident, schema, discriminant))
+    }).collect::<syn::Result<_>>()?;
+    Ok(Box::new(EnumSchema {
         ident: input.ident.clone(),
         discriminator,
         variants,
-    })
+        max_depth: attributes.max_depth,
+        depth_key: depth_key(input),
+    }))
 }
 
 struct EnumSchema {
     ident: Ident,
     discriminator: Box<dyn BinarySchema>,
     variants: Vec<(Ident, Box<dyn FieldsSchema>, Expr)>,
+    max_depth: Option<Expr>,
+    depth_key: proc_macro2::TokenStream,
 }
 
 impl BinarySchema for EnumSchema {
     fn decode(&self, ctx: &DecodeContext) -> proc_macro2::TokenStream {
         let ident = &self.ident;
+        let name = ident.to_string();
+        let location = &ctx.location;
+        let location = quote! { .in_type(#name) #location };
         let variants = self.variants.iter().map(|(variant_ident, schema, discriminant)| {
-            let decode = schema.decode(&ctx.clone());
+            let variant_name = variant_ident.to_string();
+            let decode = schema.decode(&DecodeContext {
+                wrapper: quote! { #ident::#variant_ident },
+                location: quote! { .in_variant(#variant_name) #location },
+                ..ctx.clone()
+            });
             quote! {
-                #discriminant => {
-                    Ok(#ident::#variant_ident #decode)
-                }
+                #discriminant => #decode
             }
         });
-        let encoded = &ctx.encoded;
         let offset = &ctx.offset;
+        let start = internal_ident("start");
+        let discriminant = internal_ident("discriminant");
         let decode_discriminant = self.discriminator.decode(&DecodeContext {
-            encoded: encoded.clone(),
-            offset: offset.clone(),
+            location: location.clone(),
+            ..ctx.clone()
+        });
+        let depth_key = &self.depth_key;
+        let enter = self.max_depth.as_ref().map(|max_depth| quote! {
+            let _depth = ::byten::limit::enter::<#depth_key>(#max_depth)
+                .map_err(|error| error.at(*#offset) #location)?;
         });
         quote! { {
-            let discriminant = #decode_discriminant;
-            match discriminant {
+            #enter
+            let #start = *#offset;
+            let #discriminant = #decode_discriminant;
+            match #discriminant {
                 #(#variants),*,
-                _ => Err(::byten::DecodeError::InvalidDiscriminant),
+                _ => return Err(::byten::DecodeError::InvalidDiscriminant.at(#start) #location),
             }
-        }? }
+        } }
     }
 
-    fn encode(&self, ctx: &EncodeContext) -> proc_macro2::TokenStream {
+    fn decode_from(&self, ctx: &ReadContext) -> proc_macro2::TokenStream {
         let ident = &self.ident;
-        let decoded = ctx.decoded.clone();
-        let encoded = ctx.encoded.clone();
-        let offset = ctx.offset.clone();
+        let name = ident.to_string();
+        let location = &ctx.location;
+        let location = quote! { .in_type(#name) #location };
         let variants = self.variants.iter().map(|(variant_ident, schema, discriminant)| {
-            let encoder_discriminant = self.discriminator.encode(&EncodeContext {
-                wrapper: quote! {},
-                decoded: quote! { (&#discriminant) },
-                encoded: encoded.clone(),
-                offset: offset.clone(),
-            });
-            let encode = schema.encode(&EncodeContext {
+            let variant_name = variant_ident.to_string();
+            let decode = schema.decode_from(&ReadContext {
                 wrapper: quote! { #ident::#variant_ident },
-                decoded: quote! { variant },
-                encoded: encoded.clone(),
-                offset: offset.clone(),
+                location: quote! { .in_variant(#variant_name) #location },
+                ..ctx.clone()
             });
-            let wildcard_pattern = schema.wildcard_pattern();
             quote! {
-                variant @ #ident::#variant_ident #wildcard_pattern => {
-                    #encoder_discriminant;
-                    #encode
-                }
+                #discriminant => #decode
             }
         });
-        quote! {
-            match #decoded {
-                #(#variants),*
+        let decode_discriminant = self.discriminator.decode_from(&ReadContext {
+            location: location.clone(),
+            ..ctx.clone()
+        });
+        let discriminant = internal_ident("discriminant");
+        let depth_key = &self.depth_key;
+        let enter = self.max_depth.as_ref().map(|max_depth| quote! {
+            let _depth = ::byten::limit::enter::<#depth_key>(#max_depth)
+                .map_err(|error| error #location)?;
+        });
+        quote! { {
+            #enter
+            let #discriminant = #decode_discriminant;
+            match #discriminant {
+                #(#variants),*,
+                _ => return Err(::byten::DecodeError::InvalidDiscriminant #location),
             }
-        }
+        } }
     }
 
-    fn measure(&self, ctx: &MeasureContext) -> proc_macro2::TokenStream {
+    fn encode(&self, ctx: &EncodeContext) -> proc_macro2::TokenStream {
         let ident = &self.ident;
         let decoded = ctx.decoded.clone();
+        let sink = ctx.sink.clone();
+        let variant = internal_ident("variant");
         let variants = self.variants.iter().map(|(variant_ident, schema, discriminant)| {
-            let measure_discriminant = self.discriminator.measure(&MeasureContext {
+            let encoder_discriminant = self.discriminator.encode(&EncodeContext {
                 wrapper: quote! {},
                 decoded: quote! { (&#discriminant) },
+                sink: sink.clone(),
             });
-
-            let measure = schema.measure(&MeasureContext {
+            let encode = schema.encode(&EncodeContext {
                 wrapper: quote! { #ident::#variant_ident },
-                decoded: quote! { variant },
+                decoded: quote! { #variant },
+                sink: sink.clone(),
             });
             let wildcard_pattern = schema.wildcard_pattern();
             quote! {
-                variant @ #ident::#variant_ident #wildcard_pattern => {
-                    #measure_discriminant + #measure
+                #variant @ #ident::#variant_ident #wildcard_pattern => {
+                    #encoder_discriminant;
+                    #encode
                 }
             }
         });
@@ -131,8 +153,8 @@ impl BinarySchema for EnumSchema {
         }
     }
 
-    fn measure_fixed(&self) -> proc_macro2::TokenStream {
-        panic!("Fixed measure is not yet supported for enums");
+    fn measure_fixed(&self) -> syn::Result<proc_macro2::TokenStream> {
+        Err(Error::new_spanned(&self.ident, "MeasureFixed cannot be derived for enums, whose variants may differ in size"))
     }
 }
 
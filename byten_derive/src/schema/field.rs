@@ -1,21 +1,20 @@
-use proc_macro2::Span;
-use syn::{Fields, FieldsNamed, Ident};
+use syn::{Error, Fields, FieldsNamed, Ident, ext::IdentExt};
 use quote::{ToTokens, quote};
 
-use crate::{interpret_codec_schema, parse_byten_attribute};
+use crate::interpret_field_codec_schema;
 
-use super::{BinarySchema, DecodeContext, EncodeContext, MeasureContext};
+use super::{BinarySchema, DecodeContext, EncodeContext, ReadContext, internal_ident};
 
 pub trait FieldsSchema: BinarySchema {
     fn wildcard_pattern(&self) -> proc_macro2::TokenStream;
 }
 
-pub fn interpret_fields_schema(fields: &Fields) -> Box<dyn FieldsSchema> {
-    match fields {
-        Fields::Named(fields) => Box::new(NamedFieldsSchema::interpret(fields)),
-        Fields::Unnamed(fields) => Box::new(UnnamedFieldsSchema::interpret(fields)),
+pub fn interpret_fields_schema(fields: &Fields, endian: Option<&proc_macro2::TokenStream>) -> syn::Result<Box<dyn FieldsSchema>> {
+    Ok(match fields {
+        Fields::Named(fields) => Box::new(NamedFieldsSchema::interpret(fields, endian)?),
+        Fields::Unnamed(fields) => Box::new(UnnamedFieldsSchema::interpret(fields, endian)?),
         Fields::Unit => Box::new(UnitFieldsSchema {}),
-    }
+    })
 }
 
 struct NamedFieldsSchema {
@@ -29,78 +28,85 @@ impl FieldsSchema for NamedFieldsSchema {
 }
 
 impl NamedFieldsSchema {
-    fn interpret(fields: &FieldsNamed) -> NamedFieldsSchema {
+    fn interpret(fields: &FieldsNamed, endian: Option<&proc_macro2::TokenStream>) -> syn::Result<NamedFieldsSchema> {
         let fields = fields.named.iter().map(|field| {
-            let ident = field.ident.clone().expect("Named field must have an identifier");
-            let ty = &field.ty;
-            let codec_path = parse_byten_attribute(&field.attrs).unwrap_or_else(|| syn::parse_quote!{
-                ::byten::SelfCodec::<#ty>::default()
-            });
-            let codec = interpret_codec_schema(&codec_path);
-            (ident, codec)
-        }).collect();
-        NamedFieldsSchema {
+            let ident = field.ident.clone().ok_or_else(|| Error::new_spanned(field, "named field must have an identifier"))?;
+            Ok((ident, interpret_field_codec_schema(field, endian)?))
+        }).collect::<syn::Result<_>>()?;
+        Ok(NamedFieldsSchema {
             fields,
-        }
+        })
+    }
+}
+
+impl NamedFieldsSchema {
+    // Each field is decoded into a local and then bound by reference under its own name, so
+    // codec expressions of later fields can depend on earlier ones (e.g. a byte order field).
+    fn construct(&self, wrapper: &proc_macro2::TokenStream, decodes: Vec<proc_macro2::TokenStream>) -> proc_macro2::TokenStream {
+        let idents = self.fields.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
+        let values = idents.iter()
+            .map(|ident| internal_ident(format!("value_{}", ident.unraw()).as_str()))
+            .collect::<Vec<_>>();
+        quote! { {
+            #(
+                let #values = #decodes;
+                #[allow(unused_variables)]
+                let #idents = &#values;
+            )*
+            #wrapper { #(#idents: #values),* }
+        } }
     }
 }
 
 impl BinarySchema for NamedFieldsSchema {
     fn decode(&self, ctx: &DecodeContext) -> proc_macro2::TokenStream {
-        let fields = self.fields.iter().map(|(ident, schema)| {
-            let decode = schema.decode(&ctx.clone());
-            quote! { #ident: #decode }
-        });
-        quote! { { #(#fields),* } }
+        let location = &ctx.location;
+        let decodes = self.fields.iter().map(|(ident, schema)| {
+            let name = ident.unraw().to_string();
+            schema.decode(&DecodeContext {
+                location: quote! { .in_field(#name) #location },
+                ..ctx.clone()
+            })
+        }).collect();
+        self.construct(&ctx.wrapper, decodes)
+    }
+
+    fn decode_from(&self, ctx: &ReadContext) -> proc_macro2::TokenStream {
+        let location = &ctx.location;
+        let decodes = self.fields.iter().map(|(ident, schema)| {
+            let name = ident.unraw().to_string();
+            schema.decode_from(&ReadContext {
+                location: quote! { .in_field(#name) #location },
+                ..ctx.clone()
+            })
+        }).collect();
+        self.construct(&ctx.wrapper, decodes)
     }
 
     fn encode(&self, ctx: &EncodeContext) -> proc_macro2::TokenStream {
         let wrapper = &ctx.decoded;
         let type_path = &ctx.wrapper;
         let idents = self.fields.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
-        let variables = idents.iter()
-            .map(|ident| Ident::new(format!("variant_{}", ident).as_str(), ident.span()))
-            .collect::<Vec<_>>();
-        let encodes = self.fields.iter().zip(variables.iter()).map(|((_, schema), variable)| {
+        let encodes = self.fields.iter().map(|(ident, schema)| {
             schema.encode(&EncodeContext {
                 wrapper: quote! {},
-                decoded: variable.into_token_stream(),
-                encoded: ctx.encoded.clone(),
-                offset: ctx.offset.clone(),
+                decoded: ident.into_token_stream(),
+                sink: ctx.sink.clone(),
             })
         });
         quote! { 
-            let #type_path { #(#idents: #variables,)* } = #wrapper else { unreachable!() };
+            let #type_path { #(#idents,)* } = #wrapper else { unreachable!() };
             #(#encodes;)*
         }
     }
 
-    fn measure(&self, ctx: &MeasureContext) -> proc_macro2::TokenStream {
-        let wrapper = &ctx.decoded;
-        let type_path = &ctx.wrapper;
-        let idents = self.fields.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
-        let variables = idents.iter()
-            .map(|ident| Ident::new(format!("variant_{}", ident).as_str(), ident.span()))
-            .collect::<Vec<_>>();
-        let measures = self.fields.iter().zip(variables.iter()).map(|((_, schema), variable)| {
-            schema.measure(&MeasureContext {
-                wrapper: quote! {},
-                decoded: variable.into_token_stream(),
-            })
-        });
-        quote! { {
-            let #type_path { #(#idents: #variables,)* } = #wrapper else { unreachable!() };
-            0 #( + #measures )*
-        } }
-    }
-
-    fn measure_fixed(&self) -> proc_macro2::TokenStream {
-        let measures = self.fields.iter().map(|(_, schema)| {
-            schema.measure_fixed()
-        });
-        quote! {
+    fn measure_fixed(&self) -> syn::Result<proc_macro2::TokenStream> {
+        let measures = self.fields.iter()
+            .map(|(_, schema)| schema.measure_fixed())
+            .collect::<syn::Result<Vec<_>>>()?;
+        Ok(quote! {
             0 #( + #measures )*
-        }
+        })
     }
 }
 
@@ -115,25 +121,39 @@ impl FieldsSchema for UnnamedFieldsSchema {
 }
 
 impl UnnamedFieldsSchema {
-    fn interpret(fields: &syn::FieldsUnnamed) -> UnnamedFieldsSchema {
-        let fields = fields.unnamed.iter().map(|field| {
-            if field.ident.is_some() { panic!("Unnamed field must not have an identifier"); }
-            let ty = &field.ty;
-            let codec_path = parse_byten_attribute(&field.attrs).unwrap_or_else(|| syn::parse_quote!{
-                ::byten::SelfCodec::<#ty>::default()
-            });
-            interpret_codec_schema(&codec_path)
-        }).collect();
-        UnnamedFieldsSchema { fields }
+    fn interpret(fields: &syn::FieldsUnnamed, endian: Option<&proc_macro2::TokenStream>) -> syn::Result<UnnamedFieldsSchema> {
+        let fields = fields.unnamed.iter()
+            .map(|field| interpret_field_codec_schema(field, endian))
+            .collect::<syn::Result<_>>()?;
+        Ok(UnnamedFieldsSchema { fields })
     }
 }
 
 impl BinarySchema for UnnamedFieldsSchema {
     fn decode(&self, ctx: &DecodeContext) -> proc_macro2::TokenStream {
-        let fields = self.fields.iter().map(|schema| {
-            schema.decode(&ctx.clone())
+        let location = &ctx.location;
+        let fields = self.fields.iter().enumerate().map(|(index, schema)| {
+            let name = index.to_string();
+            schema.decode(&DecodeContext {
+                location: quote! { .in_field(#name) #location },
+                ..ctx.clone()
+            })
         });
-        quote! { ( #(#fields),* ) }
+        let wrapper = &ctx.wrapper;
+        quote! { #wrapper ( #(#fields),* ) }
+    }
+
+    fn decode_from(&self, ctx: &ReadContext) -> proc_macro2::TokenStream {
+        let location = &ctx.location;
+        let fields = self.fields.iter().enumerate().map(|(index, schema)| {
+            let name = index.to_string();
+            schema.decode_from(&ReadContext {
+                location: quote! { .in_field(#name) #location },
+                ..ctx.clone()
+            })
+        });
+        let wrapper = &ctx.wrapper;
+        quote! { #wrapper ( #(#fields),* ) }
     }
 
     fn encode(&self, ctx: &EncodeContext) -> proc_macro2::TokenStream {
@@ -141,14 +161,13 @@ impl BinarySchema for UnnamedFieldsSchema {
         let wrapper = &ctx.wrapper;
         let variables = self.fields.iter()
             .enumerate()
-            .map(|(index, _)| Ident::new(format!("variant_{}", index).as_str(), Span::call_site()))
+            .map(|(index, _)| internal_ident(format!("variant_{}", index).as_str()))
             .collect::<Vec<_>>();
         let encodes = self.fields.iter().zip(variables.iter()).map(|(schema, variable)| {
             schema.encode(&EncodeContext {
                 wrapper: quote! {},
                 decoded: variable.into_token_stream(),
-                encoded: ctx.encoded.clone(),
-                offset: ctx.offset.clone(),
+                sink: ctx.sink.clone(),
             })
         });
         quote! { 
@@ -157,32 +176,13 @@ impl BinarySchema for UnnamedFieldsSchema {
         }
     }
 
-    fn measure(&self, ctx: &MeasureContext) -> proc_macro2::TokenStream {
-        let decoded = &ctx.decoded;
-        let wrapper = &ctx.wrapper;
-        let variables = self.fields.iter()
-            .enumerate()
-            .map(|(index, _)| Ident::new(format!("variant_{}", index).as_str(), Span::call_site()))
-            .collect::<Vec<_>>();
-        let measures = self.fields.iter().zip(variables.iter()).map(|(schema, variable)| {
-            schema.measure(&MeasureContext {
-                wrapper: quote! {},
-                decoded: variable.into_token_stream(),
-            })
-        });
-        quote! { {
-            let #wrapper ( #(#variables),* ) = #decoded else { unreachable!() };
-            0 #( + #measures )*
-        } }
-    }
-
-    fn measure_fixed(&self) -> proc_macro2::TokenStream {
-        let measures = self.fields.iter().map(|schema| {
-            schema.measure_fixed()
-        });
-        quote! {
+    fn measure_fixed(&self) -> syn::Result<proc_macro2::TokenStream> {
+        let measures = self.fields.iter()
+            .map(|schema| schema.measure_fixed())
+            .collect::<syn::Result<Vec<_>>>()?;
+        Ok(quote! {
             0 #( + #measures )*
-        }
+        })
     }
 }
 
@@ -195,20 +195,20 @@ impl FieldsSchema for UnitFieldsSchema {
 }
 
 impl BinarySchema for UnitFieldsSchema {
-    fn decode(&self, _ctx: &DecodeContext) -> proc_macro2::TokenStream {
-        quote! {}
+    fn decode(&self, ctx: &DecodeContext) -> proc_macro2::TokenStream {
+        ctx.wrapper.clone()
     }
 
-    fn encode(&self, _ctx: &EncodeContext) -> proc_macro2::TokenStream {
-        quote! {}
+    fn decode_from(&self, ctx: &ReadContext) -> proc_macro2::TokenStream {
+        ctx.wrapper.clone()
     }
 
-    fn measure(&self, _ctx: &MeasureContext) -> proc_macro2::TokenStream {
-        quote! { 0 }
+    fn encode(&self, _ctx: &EncodeContext) -> proc_macro2::TokenStream {
+        quote! {}
     }
 
-    fn measure_fixed(&self) -> proc_macro2::TokenStream {
-        quote! { 0 }
+    fn measure_fixed(&self) -> syn::Result<proc_macro2::TokenStream> {
+        Ok(quote! { 0 })
     }
 }
 
@@ -0,0 +1,102 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{ToTokens, quote};
+use syn::{Data, DeriveInput, Field, GenericParam, Generics, Ident, Lifetime, LifetimeParam, Type, WherePredicate, parse_quote};
+
+use super::{Predicates, parse_byten_attributes};
+
+pub enum DerivedTrait {
+    Decode,
+    DecodeFrom,
+    Encode,
+    MeasureFixed,
+}
+
+// The lifetime of the input borrowed by `Decode`; a container that already declares `'encoded`
+// decodes from it directly, otherwise it is added and outlives every declared lifetime.
+pub fn encoded_lifetime() -> Lifetime {
+    parse_quote! { 'encoded }
+}
+
+// Generics for the impl of `derived`: the container's own, plus `'encoded` for `Decode`, plus
+// either the `bound` override or a bound on every type parameter that appears in a field.
+pub fn impl_generics(input: &DeriveInput, derived: DerivedTrait) -> syn::Result<Generics> {
+    let attributes = parse_byten_attributes(&input.attrs)?;
+    let bounds = attributes.bounds;
+    let mut generics = input.generics.clone();
+
+    let encoded = encoded_lifetime();
+    if matches!(derived, DerivedTrait::Decode) && !generics.lifetimes().any(|param| param.lifetime == encoded) {
+        let outlived = generics.lifetimes().map(|param| param.lifetime.clone()).collect();
+        generics.params.insert(0, GenericParam::Lifetime(LifetimeParam {
+            bounds: outlived,
+            ..LifetimeParam::new(encoded.clone())
+        }));
+    }
+
+    let (trait_bound, bound_override) = match derived {
+        DerivedTrait::Decode => (quote! { ::byten::Decode<#encoded> }, bounds.decode),
+        DerivedTrait::DecodeFrom => (quote! { ::byten::DecodeFrom }, bounds.decode_from),
+        DerivedTrait::Encode => (quote! { ::byten::Encode }, bounds.encode),
+        DerivedTrait::MeasureFixed => (quote! { ::byten::MeasureFixed }, bounds.measure_fixed),
+    };
+    let predicates = match bound_override.or(bounds.all) {
+        Some(predicates) => predicates,
+        None => {
+            let field_types = field_types(&input.data)?;
+            input.generics.type_params()
+                .filter(|param| field_types.iter().any(|ty| mentions(ty.to_token_stream(), &param.ident)))
+                .map(|param| -> WherePredicate {
+                    let ident = &param.ident;
+                    parse_quote! { #ident: #trait_bound }
+                })
+                .collect::<Predicates>()
+        },
+    };
+    generics.make_where_clause().predicates.extend(predicates);
+
+    // Nesting is counted per `TypeId` of `depth_key`, which needs every type parameter to be `'static`.
+    if attributes.max_depth.is_some() && matches!(derived, DerivedTrait::Decode | DerivedTrait::DecodeFrom) {
+        let statics = input.generics.type_params().map(|param| -> WherePredicate {
+            let ident = &param.ident;
+            parse_quote! { #ident: 'static }
+        }).collect::<Vec<_>>();
+        generics.make_where_clause().predicates.extend(statics);
+    }
+    Ok(generics)
+}
+
+// The container type with every lifetime replaced by `'static`, naming it apart from all other
+// types without borrowing from the input; depth limits are keyed by it.
+pub fn depth_key(input: &DeriveInput) -> TokenStream {
+    let ident = &input.ident;
+    let params = input.generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(_) => quote! { 'static },
+        GenericParam::Type(param) => param.ident.to_token_stream(),
+        GenericParam::Const(param) => param.ident.to_token_stream(),
+    });
+    quote! { #ident<#(#params),*> }
+}
+
+// Types of the encoded fields; skipped ones need no codec and so no bound.
+fn field_types(data: &Data) -> syn::Result<Vec<&Type>> {
+    let fields: Vec<&Field> = match data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data.variants.iter().flat_map(|variant| variant.fields.iter()).collect(),
+        Data::Union(data) => data.fields.named.iter().collect(),
+    };
+    let mut types = Vec::new();
+    for field in fields {
+        if parse_byten_attributes(&field.attrs)?.skip.is_none() {
+            types.push(&field.ty);
+        }
+    }
+    Ok(types)
+}
+
+fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(candidate) => candidate == *ident,
+        TokenTree::Group(group) => mentions(group.stream(), ident),
+        _ => false,
+    })
+}
@@ -3,35 +3,61 @@ pub mod r#struct;
 pub mod r#enum;
 pub mod field;
 pub mod codec;
+pub mod attribute;
+pub mod generics;
 
 pub use r#struct::*;
 pub use r#enum::*;
 pub use field::*;
 pub use codec::*;
+pub use attribute::*;
+pub use generics::*;
+
+pub fn interpret_schema(input: &syn::DeriveInput, derive: &str) -> syn::Result<Box<dyn BinarySchema>> {
+    match &input.data {
+        syn::Data::Struct(data) => interpret_struct_schema(input, data),
+        syn::Data::Enum(data) => interpret_enum_schema(input, data),
+        syn::Data::Union(data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            format!("{} can only be derived for structs and enums", derive),
+        )),
+    }
+}
 
 pub trait BinarySchema {
     fn decode(&self, _ctx: &DecodeContext) -> proc_macro2::TokenStream { unimplemented!() }
+    fn decode_from(&self, _ctx: &ReadContext) -> proc_macro2::TokenStream { unimplemented!() }
     fn encode(&self, _ctx: &EncodeContext) -> proc_macro2::TokenStream { unimplemented!() }
-    fn measure_fixed(&self) -> proc_macro2::TokenStream { unimplemented!() }
-    fn measure(&self, _ctx: &MeasureContext) -> proc_macro2::TokenStream { unimplemented!() }
+    fn measure_fixed(&self) -> syn::Result<proc_macro2::TokenStream> { unimplemented!() }
 }
 
 #[derive(Clone)]
 pub struct DecodeContext {
+    pub wrapper: proc_macro2::TokenStream,
     pub encoded: proc_macro2::TokenStream,
     pub offset: proc_macro2::TokenStream,
+    pub budget: proc_macro2::TokenStream,
+    // `DecodeError` builder calls that attach the field path, innermost first.
+    pub location: proc_macro2::TokenStream,
 }
 
 #[derive(Clone)]
-pub struct EncodeContext {
+pub struct ReadContext {
     pub wrapper: proc_macro2::TokenStream,
-    pub decoded: proc_macro2::TokenStream,
-    pub encoded: proc_macro2::TokenStream,
-    pub offset: proc_macro2::TokenStream,
+    pub reader: proc_macro2::TokenStream,
+    pub budget: proc_macro2::TokenStream,
+    pub location: proc_macro2::TokenStream,
 }
 
 #[derive(Clone)]
-pub struct MeasureContext {
+pub struct EncodeContext {
     pub wrapper: proc_macro2::TokenStream,
     pub decoded: proc_macro2::TokenStream,
+    pub sink: proc_macro2::TokenStream,
+}
+
+// Identifiers the generated code introduces itself resolve at the macro's definition site, so
+// they can neither shadow nor be shadowed by field names referenced from `#[byten(...)]` codecs.
+pub fn internal_ident(name: &str) -> proc_macro2::Ident {
+    proc_macro2::Ident::new(name, proc_macro2::Span::mixed_site())
 }
@@ -1,39 +1,70 @@
-use syn::{Data, DeriveInput, Ident};
+use syn::{DataStruct, DeriveInput, Expr, Ident};
 use quote::quote;
 
-use super::{BinarySchema, DecodeContext, EncodeContext, MeasureContext, interpret_fields_schema};
+use super::{BinarySchema, DecodeContext, EncodeContext, ReadContext, depth_key, interpret_fields_schema, interpret_magic_schema, parse_byten_attributes};
 
-pub fn interpret_struct_schema(input: &DeriveInput) -> Box<dyn BinarySchema> {
-    let Data::Struct(ref data) = input.data else {
-        panic!("StructSchema can only be created from struct data");
-    };
-    Box::new(StructSchema {
+pub fn interpret_struct_schema(input: &DeriveInput, data: &DataStruct) -> syn::Result<Box<dyn BinarySchema>> {
+    let attributes = parse_byten_attributes(&input.attrs)?;
+    Ok(Box::new(StructSchema {
         ident: input.ident.clone(),
-        fields: interpret_fields_schema(&data.fields),
-    })
+        fields: interpret_magic_schema(attributes.magic, interpret_fields_schema(&data.fields, attributes.endian.as_ref())?),
+        max_depth: attributes.max_depth,
+        depth_key: depth_key(input),
+    }))
 }
 
 struct StructSchema {
     ident: Ident,
     fields: Box<dyn BinarySchema>,
+    max_depth: Option<Expr>,
+    depth_key: proc_macro2::TokenStream,
 }
 
 impl BinarySchema for StructSchema {
     fn decode(&self, ctx: &DecodeContext) -> proc_macro2::TokenStream {
-        let ident = &self.ident;
-        let fields = self.fields.decode(&ctx.clone());
-        quote! { #ident #fields }
+        let name = self.ident.to_string();
+        let location = &ctx.location;
+        let location = quote! { .in_type(#name) #location };
+        let fields = self.fields.decode(&DecodeContext {
+            location: location.clone(),
+            ..ctx.clone()
+        });
+        let Some(max_depth) = &self.max_depth else {
+            return fields;
+        };
+        let depth_key = &self.depth_key;
+        let offset = &ctx.offset;
+        quote! { {
+            let _depth = ::byten::limit::enter::<#depth_key>(#max_depth)
+                .map_err(|error| error.at(*#offset) #location)?;
+            #fields
+        } }
     }
 
-    fn encode(&self, ctx: &EncodeContext) -> proc_macro2::TokenStream {
-        self.fields.encode(&ctx.clone())
+    fn decode_from(&self, ctx: &ReadContext) -> proc_macro2::TokenStream {
+        let name = self.ident.to_string();
+        let location = &ctx.location;
+        let location = quote! { .in_type(#name) #location };
+        let fields = self.fields.decode_from(&ReadContext {
+            location: location.clone(),
+            ..ctx.clone()
+        });
+        let Some(max_depth) = &self.max_depth else {
+            return fields;
+        };
+        let depth_key = &self.depth_key;
+        quote! { {
+            let _depth = ::byten::limit::enter::<#depth_key>(#max_depth)
+                .map_err(|error| error #location)?;
+            #fields
+        } }
     }
 
-    fn measure(&self, ctx: &MeasureContext) -> proc_macro2::TokenStream {
-        self.fields.measure(&ctx.clone())
+    fn encode(&self, ctx: &EncodeContext) -> proc_macro2::TokenStream {
+        self.fields.encode(&ctx.clone())
     }
 
-    fn measure_fixed(&self) -> proc_macro2::TokenStream {
+    fn measure_fixed(&self) -> syn::Result<proc_macro2::TokenStream> {
         self.fields.measure_fixed()
     }
 }
@@ -1,10 +1,12 @@
+use std::num::{NonZeroU8, NonZeroU32, Wrapping};
+
 use byten::{
-    Decode, DecodeOwned, Encode, Measure, MeasureFixed, SelfCodec, prim::{U16BE, U16LE, U32BE, U64BE}, util::Convert, var
+    Decode, DecodeFrom, DecodeOwned, Decoder, Encode, Encoder, Measure, MeasureFixed, SelfCodec, fix, prim::{self, ByteOrder, U16BE, U16LE, U32BE, U64BE}, util::{self, Convert}, var
 };
 
 type U8AsUSize = Convert<SelfCodec<u8>,usize>;
 
-#[derive(Debug, DecodeOwned, PartialEq, Eq, Encode, Measure)]
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode)]
 struct Person {
     #[byten(U32BE)]
     id: u32,
@@ -15,7 +17,7 @@ struct Person {
     favorite_colors: Vec<Color>,
 }
 
-#[derive(Debug, DecodeOwned, PartialEq, Eq, Encode, MeasureFixed)]
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode, MeasureFixed)]
 struct Date {
     day: u8,
     month: u8,
@@ -23,7 +25,8 @@ struct Date {
     year: u16,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, DecodeOwned, Encode, Measure)]
+#[derive(Clone, Debug, PartialEq, Eq, DecodeOwned, DecodeFrom, Encode)]
+#[allow(clippy::enum_variant_names)]
 #[repr(u16)]
 #[byten(U16LE)]
 enum Color {
@@ -49,15 +52,236 @@ enum Color {
     Unknown() = 255,
 }
 
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode)]
+#[byten(max_depth = 4)]
+struct Node {
+    #[byten(var::Option::<SelfCodec<Box<Node>>>::default())]
+    child: Option<Box<Node>>,
+}
+
+// Borrows from the input, so its nesting is counted under `Chain<'static>`.
+#[derive(Debug, Decode, PartialEq, Eq, Encode)]
+#[byten(max_depth = 2)]
+struct Chain<'a> {
+    #[byten(var::Slice::<var::USizeBE>::default())]
+    head: &'a [u8],
+    #[byten(var::Option::<SelfCodec<Box<Chain<'a>>>>::default())]
+    tail: Option<Box<Chain<'a>>>,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode)]
+struct List {
+    value: u8,
+    #[byten(var::Option::<SelfCodec<Box<List>>>::default())]
+    next: Option<Box<List>>,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode)]
+#[byten(max_depth = 64)]
+#[repr(u8)]
+enum Tree {
+    Leaf(Leaf) = 0,
+    Branch(Box<Tree>, Box<Tree>) = 1,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode)]
+#[byten(max_depth = 2)]
+struct Leaf {
+    value: u8,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Encode)]
+#[byten(endian = "le")]
+struct Sample {
+    channel: u16,
+    timestamp: u32,
+    #[byten(U32BE)]
+    sequence: u32,
+    offset: i64,
+    value: f32,
+    kind: Kind,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Encode)]
+#[byten(endian = "be")]
+#[repr(u16)]
+enum Kind {
+    Raw(u8) = 1,
+    Scaled(u16, i16) = 2,
+}
+
+fn byte_order(little_endian: &bool) -> ByteOrder {
+    if *little_endian { ByteOrder::Little } else { ByteOrder::Big }
+}
+
+// Later fields pick their byte order from the decoded `little_endian` flag.
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Encode)]
+struct Record {
+    little_endian: bool,
+    #[byten(prim::U16Dyn::codec(byte_order(little_endian)))]
+    offset: u16,
+    #[byten(prim::I24Dyn::codec(byte_order(little_endian)))]
+    delta: i32,
+    #[byten(var::Vec::codec(var::USizeBE, prim::U32Dyn::codec(byte_order(little_endian))))]
+    values: Vec<u32>,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Encode)]
+struct Scalars {
+    delta: i8,
+    unit: (),
+    letter: char,
+    #[byten(prim::CharLE)]
+    symbol: char,
+    id: NonZeroU8,
+    #[byten(util::NonZero::codec(U32BE))]
+    count: NonZeroU32,
+    counter: Wrapping<u8>,
+    #[byten(util::Wrapping::codec(U16BE))]
+    sequence: Wrapping<u16>,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode, MeasureFixed)]
+struct Calendar {
+    holidays: [Date; 2],
+    weekdays: [bool; 7],
+    checksum: [u8; 4],
+}
+
+#[derive(Clone, Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode)]
+struct Packet<P> {
+    #[byten(U16BE)]
+    id: u16,
+    payload: P,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode)]
+struct Batch<T: Clone> where T: Eq {
+    #[byten(var::Vec::<var::USizeBE, SelfCodec::<T>>::default())]
+    items: Vec<T>,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode, MeasureFixed)]
+#[byten(endian = "le")]
+struct Window<T, const N: usize> {
+    start: u32,
+    samples: [T; N],
+}
+
+#[derive(Debug, Decode, PartialEq, Eq, Encode)]
+struct View<'a> {
+    #[byten(var::Slice::<var::USizeBE>::default())]
+    bytes: &'a [u8],
+    #[byten(var::str::Str::<var::USizeBE>::default())]
+    text: &'a str,
+}
+
+#[derive(Debug, Decode, DecodeFrom, PartialEq, Encode)]
+struct Ping {
+    #[byten(U32BE)]
+    sequence: u32,
+    origin: Kind,
+}
+
+// Encodes as nothing whatever `T` is, so `Tagged` needs no bound on its tag.
+#[derive(Debug, PartialEq, Eq)]
+struct Marker<T>(std::marker::PhantomData<T>);
+
+impl<T> Decode<'_> for Marker<T> {
+    fn decode(_: &[u8], _: &mut usize) -> Result<Self, byten::DecodeError> {
+        Ok(Marker(std::marker::PhantomData))
+    }
+}
+
+impl<T> Encode for Marker<T> {
+    fn encode<S: byten::Sink + ?Sized>(&self, _: &mut S) -> Result<(), byten::EncodeError> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, DecodeOwned, PartialEq, Eq, Encode)]
+#[byten(bound = "")]
+struct Tagged<T> {
+    tag: Marker<T>,
+    value: u8,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Opaque;
+
+// A codec with no `ReadDecoder` impl, which a field may still use as long as nothing derives `DecodeFrom`.
+struct Inverted;
+
+impl Decoder<'_, '_> for Inverted {
+    type Decoded = u8;
+    fn decode(&self, encoded: &[u8], offset: &mut usize) -> Result<u8, byten::DecodeError> {
+        u8::decode(encoded, offset).map(|byte| !byte)
+    }
+}
+
+impl Encoder for Inverted {
+    type Decoded = u8;
+    fn encode<S: byten::Sink + ?Sized>(&self, decoded: &u8, sink: &mut S) -> Result<(), byten::EncodeError> {
+        (!decoded).encode(sink)
+    }
+}
+
+#[derive(Debug, DecodeOwned, PartialEq, Eq, Encode)]
+struct Masked {
+    #[byten(Inverted)]
+    mask: u8,
+    value: u8,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode)]
+struct Cached<T> {
+    #[byten(var::str::String::<var::USizeBE>::default())]
+    name: String,
+    #[byten(skip = name.len())]
+    name_length: usize,
+    #[byten(skip)]
+    lookups: std::cell::Cell<u32>,
+    #[byten(skip)]
+    marker: std::marker::PhantomData<T>,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode, MeasureFixed)]
+struct Stamped(#[byten(U32BE)] u32, #[byten(skip)] Option<Box<Date>>, u8);
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode)]
+#[repr(u8)]
+enum Event {
+    Tick { count: u8, #[byten(skip = true)] fresh: bool } = 1,
+    Tock(#[byten(skip)] String) = 2,
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode, MeasureFixed)]
+#[byten(magic = b"\x89PNG\r\n\x1a\n")]
+struct ImageHeader {
+    #[byten(magic = b"IHDR", U32BE)]
+    width: u32,
+    #[byten(U32BE)]
+    height: u32,
+    #[byten(fix::Const::codec(U16BE, 2))]
+    version: (),
+}
+
+#[derive(Debug, DecodeOwned, DecodeFrom, PartialEq, Eq, Encode)]
+#[repr(u8)]
+#[byten(magic = b"SG")]
+enum Signal {
+    Start = 1,
+    Stop(u8) = 2,
+}
+
 #[cfg(test)]
 mod test {
-    use byten::prelude::EncodeToVec;
+    use byten::{EncodeTo, prelude::EncodeToVec, sink::SliceSink};
 
     use super::*;
-    
-    #[test]
-    fn test_person_codec() {
-        let person = Person {
+
+    fn person() -> Person {
+        Person {
             id: 123456,
             name: "Alice".to_string(),
             birthday: Date {
@@ -78,7 +302,12 @@ mod test {
                 Color::ColorCode(0b110010101010),
                 Color::Unknown(),
             ],
-        };
+        }
+    }
+    
+    #[test]
+    fn test_person_codec() {
+        let person = person();
 
         let expected_encoded = vec![
             0x00, 0x01, 0xe2, 0x40, // id: U32BE(123456)
@@ -121,7 +350,412 @@ mod test {
         
         let encoded = person.encode_to_vec().expect("Encoding failed");
         assert_eq!(encoded, expected_encoded);
+
+        let mut buffer = vec![0u8; size];
+        person.encode(&mut SliceSink::new(&mut buffer, &mut 0)).expect("Slice encoding failed");
+        assert_eq!(buffer, expected_encoded);
+
+        let mut short_buffer = vec![0u8; size - 1];
+        let error = person.encode(&mut SliceSink::new(&mut short_buffer, &mut 0)).expect_err("Encoding into a short buffer succeeded");
+        assert!(matches!(error, byten::EncodeError::BufferTooSmall));
+
+        let mut writer = std::io::Cursor::new(Vec::new());
+        person.encode_to(&mut writer).expect("Writing failed");
+        assert_eq!(writer.into_inner(), expected_encoded);
+
         let decoded = Person::decode(&encoded, &mut 0).expect("Decoding failed");
         assert_eq!(person, decoded);
     }
+
+    #[test]
+    fn test_person_decode_from_reader() {
+        let person = person();
+        let encoded = person.encode_to_vec().expect("Encoding failed");
+
+        let mut reader = encoded.as_slice();
+        let decoded = Person::decode_from(&mut reader).expect("Reading failed");
+        assert_eq!(person, decoded);
+        assert!(reader.is_empty());
+
+        let mut truncated = &encoded[..encoded.len() - 1];
+        let error = Person::decode_from(&mut truncated).expect_err("Reading truncated input succeeded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::Incomplete { .. }));
+
+        let mut offset = 0;
+        let progress = Person::try_decode(&encoded[..encoded.len() - 1], &mut offset).expect("Truncated input reported as malformed");
+        assert!(matches!(progress, byten::Progress::Incomplete { needed: Some(1) }));
+        assert_eq!(offset, 0);
+    }
+
+    #[test]
+    fn test_person_decode_error_location() {
+        let mut encoded = person().encode_to_vec().expect("Encoding failed");
+        // Corrupt the discriminant of the second color inside the gradient.
+        encoded[32] = 0x09;
+
+        let error = Person::decode(&encoded, &mut 0).expect_err("Decoding an invalid discriminant succeeded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::InvalidDiscriminant));
+        assert_eq!(error.offset(), Some(32));
+        assert_eq!(error.path().expect("Error has no path").to_string(), "Person.favorite_colors[3].Gradient.1");
+        assert_eq!(error.to_string(), "Invalid discriminant in Person.favorite_colors[3].Gradient.1 at offset 32");
+
+        let error = Person::decode_from(&mut encoded.as_slice()).expect_err("Reading an invalid discriminant succeeded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::InvalidDiscriminant));
+        assert_eq!(error.offset(), None);
+        assert_eq!(error.path().expect("Error has no path").to_string(), "Person.favorite_colors[3].Gradient.1");
+    }
+
+    #[test]
+    fn test_person_hostile_input() {
+        let encoded = person().encode_to_vec().expect("Encoding failed");
+        let mut rng = fastrand::Rng::with_seed(0xb7e0);
+        for _ in 0..5000 {
+            let mut input = match rng.u8(0..3) {
+                0 => (0..rng.usize(0..96)).map(|_| rng.u8(..)).collect(),
+                1 => encoded[..rng.usize(0..=encoded.len())].to_vec(),
+                _ => encoded.clone(),
+            };
+            for _ in 0..rng.usize(0..4) {
+                if let Some(byte) = rng.choice(input.iter_mut()) {
+                    *byte = rng.u8(..);
+                }
+            }
+
+            let mut offset = 0;
+            if Person::decode(&input, &mut offset).is_ok() {
+                assert!(offset <= input.len());
+            }
+            let _ = Person::decode_from(&mut input.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_person_budget() {
+        let encoded = person().encode_to_vec().expect("Encoding failed");
+        let colors = person().favorite_colors.len();
+
+        let mut budget = byten::limit::Budget::new().with_max_items(colors - 1);
+        let error = Person::decode_with(&encoded, &mut 0, &mut budget).expect_err("Decoding past the item budget succeeded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::BudgetExceeded));
+        assert_eq!(error.path().expect("Error has no path").to_string(), "Person.favorite_colors");
+
+        let mut budget = byten::limit::Budget::new().with_max_items(colors);
+        let error = Person::decode_from_with(&mut encoded.as_slice(), &mut budget.with_max_items(colors - 1))
+            .expect_err("Reading past the item budget succeeded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::BudgetExceeded));
+        assert_eq!(Person::decode_with(&encoded, &mut 0, &mut budget).expect("Decoding within the budget failed"), person());
+        assert_eq!(budget.max_items, 0);
+    }
+
+    #[test]
+    fn test_recursion_depth_limit() {
+        let node = (1..4).fold(Node { child: None }, |child, _| Node { child: Some(Box::new(child)) });
+        let encoded = node.encode_to_vec().expect("Encoding failed");
+        assert_eq!(Node::decode(&encoded, &mut 0).expect("Decoding failed"), node);
+
+        let node = Node { child: Some(Box::new(node)) };
+        let encoded = node.encode_to_vec().expect("Encoding failed");
+        let error = Node::decode(&encoded, &mut 0).expect_err("Decoding past the max depth succeeded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::DepthLimitExceeded));
+        let error = Node::decode_from(&mut encoded.as_slice()).expect_err("Reading past the max depth succeeded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::DepthLimitExceeded));
+
+        // Each guarded type counts its own nesting, so the leaves stay within their limit at any depth.
+        let tree = (0..4).fold(Tree::Leaf(Leaf { value: 0 }), |tree, level| {
+            Tree::Branch(Box::new(tree), Box::new(Tree::Leaf(Leaf { value: level })))
+        });
+        let encoded = tree.encode_to_vec().expect("Encoding failed");
+        assert_eq!(Tree::decode(&encoded, &mut 0).expect("Decoding failed"), tree);
+        assert_eq!(Tree::decode_from(&mut encoded.as_slice()).expect("Reading failed"), tree);
+
+        // A guarded type fails instead of overflowing the stack.
+        let mut encoded = [0x01].repeat(100_000);
+        encoded.extend([0x00, 0x00, 0x00, 0x00]);
+        let error = Tree::decode(&encoded, &mut 0).expect_err("Decoding a deeply nested tree succeeded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::DepthLimitExceeded));
+        let error = Tree::decode_from(&mut encoded.as_slice()).expect_err("Reading a deeply nested tree succeeded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::DepthLimitExceeded));
+
+        // Boxes nest without limit unless a type or codec asks for one.
+        let list = (0..200).fold(List { value: 0, next: None }, |next, value| List { value, next: Some(Box::new(next)) });
+        let encoded = list.encode_to_vec().expect("Encoding failed");
+        assert_eq!(List::decode(&encoded, &mut 0).expect("Decoding failed"), list);
+        let codec = SelfCodec::<List>::codec().with_max_depth(1);
+        assert_eq!(codec.max_depth(), Some(1));
+        assert_eq!(byten::Decoder::decode(&codec, &encoded, &mut 0).expect("Decoding failed"), list);
+
+        let chain = Chain { head: b"a", tail: Some(Box::new(Chain { head: b"b", tail: None })) };
+        let encoded = chain.encode_to_vec().expect("Encoding failed");
+        assert_eq!(Chain::decode(&encoded, &mut 0).expect("Decoding failed"), chain);
+        let chain = Chain { head: b"c", tail: Some(Box::new(chain)) };
+        let encoded = chain.encode_to_vec().expect("Encoding failed");
+        let error = Chain::decode(&encoded, &mut 0).expect_err("Decoding past the max depth succeeded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::DepthLimitExceeded));
+    }
+
+    #[test]
+    fn test_container_endian() {
+        let sample = Sample {
+            channel: 0x0102,
+            timestamp: 0x03040506,
+            sequence: 0x0708090a,
+            offset: -2,
+            value: 1.5,
+            kind: Kind::Scaled(0x0b0c, -1),
+        };
+        let expected_encoded = vec![
+            0x02, 0x01,             // channel: u16 LE
+            0x06, 0x05, 0x04, 0x03, // timestamp: u32 LE
+            0x07, 0x08, 0x09, 0x0a, // sequence: explicit U32BE
+            0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // offset: i64 LE
+            0x00, 0x00, 0xc0, 0x3f, // value: f32 LE
+            0x00, 0x02,             // Kind::Scaled discriminant: u16 BE
+            0x0b, 0x0c,             // u16 BE
+            0xff, 0xff,             // i16 BE
+        ];
+
+        let encoded = sample.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, expected_encoded);
+        assert_eq!(sample.measure().expect("Measuring failed"), expected_encoded.len());
+        assert_eq!(Sample::decode(&encoded, &mut 0).expect("Decoding failed"), sample);
+    }
+
+    #[test]
+    fn test_runtime_byte_order() {
+        let record = Record {
+            little_endian: true,
+            offset: 0x0102,
+            delta: -2,
+            values: vec![0x03040506],
+        };
+        let expected_encoded = vec![
+            0x01,                   // little_endian
+            0x02, 0x01,             // offset: u16 LE
+            0xfe, 0xff, 0xff,       // delta: i24 LE
+            0x01,                   // values length: usize BE varint
+            0x06, 0x05, 0x04, 0x03, // u32 LE
+        ];
+
+        let encoded = record.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, expected_encoded);
+        assert_eq!(record.measure().expect("Measuring failed"), expected_encoded.len());
+        assert_eq!(Record::decode(&encoded, &mut 0).expect("Decoding failed"), record);
+
+        let record = Record { little_endian: false, ..record };
+        let encoded = record.encode_to_vec().expect("Encoding failed");
+        assert_eq!(&encoded[1..3], [0x01, 0x02]);
+        assert_eq!(Record::decode_from(&mut encoded.as_slice()).expect("Reading failed"), record);
+    }
+
+    #[test]
+    fn test_scalars() {
+        let scalars = Scalars {
+            delta: -2,
+            unit: (),
+            letter: 'é',
+            symbol: '🦀',
+            id: NonZeroU8::new(7).unwrap(),
+            count: NonZeroU32::new(0x0102).unwrap(),
+            counter: Wrapping(255),
+            sequence: Wrapping(0x0304),
+        };
+        let expected_encoded = vec![
+            0xfe,                   // delta: i8
+            0xc3, 0xa9,             // letter: UTF-8
+            0x80, 0xf9, 0x01, 0x00, // symbol: 4-byte scalar LE
+            0x07,                   // id
+            0x00, 0x00, 0x01, 0x02, // count: U32BE
+            0xff,                   // counter
+            0x03, 0x04,             // sequence: U16BE
+        ];
+
+        let encoded = scalars.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, expected_encoded);
+        assert_eq!(scalars.measure().expect("Measuring failed"), expected_encoded.len());
+        assert_eq!(Scalars::decode(&encoded, &mut 0).expect("Decoding failed"), scalars);
+        assert_eq!(Scalars::decode_from(&mut encoded.as_slice()).expect("Reading failed"), scalars);
+
+        let mut zero_id = expected_encoded.clone();
+        zero_id[7] = 0;
+        let error = Scalars::decode(&zero_id, &mut 0).expect_err("Zero decoded as NonZeroU8");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::ZeroValue));
+        assert_eq!(error.to_string(), "Zero value for a non-zero type in Scalars.id at offset 7");
+
+        let mut zero_count = expected_encoded.clone();
+        zero_count[8..12].fill(0);
+        let error = Scalars::decode_from(&mut zero_count.as_slice()).expect_err("Zero decoded as NonZeroU32");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::ZeroValue));
+
+        let mut invalid_letter = expected_encoded.clone();
+        invalid_letter[2] = 0x29;
+        assert!(Scalars::decode(&invalid_letter, &mut 0).is_err());
+    }
+
+    #[test]
+    fn test_arrays() {
+        let calendar = Calendar {
+            holidays: [
+                Date { day: 1, month: 1, year: 2025 },
+                Date { day: 25, month: 12, year: 2025 },
+            ],
+            weekdays: [true, true, true, true, true, false, false],
+            checksum: [0xde, 0xad, 0xbe, 0xef],
+        };
+        let expected_encoded = vec![
+            1, 1, 0x07, 0xe9,
+            25, 12, 0x07, 0xe9,
+            1, 1, 1, 1, 1, 0, 0,
+            0xde, 0xad, 0xbe, 0xef,
+        ];
+
+        let encoded = calendar.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, expected_encoded);
+        assert_eq!(Calendar::measure_fixed(), expected_encoded.len());
+        assert_eq!(Calendar::decode(&encoded, &mut 0).expect("Decoding failed"), calendar);
+        assert_eq!(Calendar::decode_from(&mut encoded.as_slice()).expect("Reading failed"), calendar);
+
+        let mut invalid = expected_encoded.clone();
+        invalid[13] = 2;
+        let error = Calendar::decode(&invalid, &mut 0).expect_err("Invalid bool decoded");
+        assert_eq!(error.to_string(), "Invalid data in Calendar.weekdays[5] at offset 13");
+
+        let error = Calendar::decode(&expected_encoded[..6], &mut 0).expect_err("Truncated input decoded");
+        assert_eq!(error.path().map(ToString::to_string).as_deref(), Some("Calendar.holidays[1].year"));
+    }
+
+    #[test]
+    fn test_generics() {
+        let packet = Packet { id: 7, payload: Date { day: 1, month: 2, year: 2024 } };
+        let encoded = packet.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, vec![0x00, 0x07, 1, 2, 0x07, 0xe8]);
+        assert_eq!(packet.measure().expect("Measuring failed"), encoded.len());
+        assert_eq!(Packet::<Date>::decode(&encoded, &mut 0).expect("Decoding failed"), packet);
+        assert_eq!(Packet::<Date>::decode_from(&mut encoded.as_slice()).expect("Reading failed"), packet);
+
+        let batch = Batch { items: vec![Packet { id: 1, payload: 0xaa_u8 }, Packet { id: 2, payload: 0xbb }] };
+        let encoded = batch.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, vec![2, 0x00, 0x01, 0xaa, 0x00, 0x02, 0xbb]);
+        assert_eq!(Batch::<Packet<u8>>::decode(&encoded, &mut 0).expect("Decoding failed"), batch);
+
+        let window = Window { start: 9, samples: [Date { day: 3, month: 4, year: 2000 }, Date { day: 3, month: 4, year: 2000 }] };
+        let encoded = window.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, vec![9, 0, 0, 0, 3, 4, 0x07, 0xd0, 3, 4, 0x07, 0xd0]);
+        assert_eq!(Window::<Date, 2>::measure_fixed(), encoded.len());
+        assert_eq!(Window::<Date, 2>::decode(&encoded, &mut 0).expect("Decoding failed"), window);
+
+        let view = View { bytes: &[1, 2, 3], text: "hi" };
+        let encoded = view.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, vec![3, 1, 2, 3, 2, b'h', b'i']);
+        assert_eq!(View::decode(&encoded, &mut 0).expect("Decoding failed"), view);
+
+        let ping = Ping { sequence: 9, origin: Kind::Raw(4) };
+        let encoded = ping.encode_to_vec().expect("Encoding failed");
+        assert_eq!(Ping::decode_from(&mut encoded.as_slice()).expect("Reading failed"), ping);
+
+        let tagged = Tagged::<Opaque> { tag: Marker(std::marker::PhantomData), value: 5 };
+        let encoded = tagged.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, vec![5]);
+        assert_eq!(Tagged::<Opaque>::decode(&encoded, &mut 0).expect("Decoding failed"), tagged);
+    }
+
+    fn assert_decode_owned<T: DecodeOwned + Encode + PartialEq + std::fmt::Debug>(value: T) {
+        let encoded = value.encode_to_vec().expect("Encoding failed");
+        let mut offset = 0;
+        assert_eq!(T::decode_owned(&encoded, &mut offset).expect("Decoding failed"), value);
+        assert_eq!(offset, encoded.len());
+    }
+
+    #[test]
+    fn test_decode_owned() {
+        assert_decode_owned(Person {
+            id: 1,
+            name: "Ada".to_string(),
+            birthday: Date { day: 10, month: 12, year: 1815 },
+            favorite_colors: vec![Color::Red, Color::Gradient(Box::new(Color::Blue), Box::new(Color::Grayscale(9)))],
+        });
+        assert_decode_owned(Color::RGBa { red: 1, green: 2, blue: 3, alpha: 4 });
+        assert_decode_owned(Box::new(Node { child: Some(Box::new(Node { child: None })) }));
+        assert_decode_owned(Packet { id: 3, payload: Batch { items: vec![1_u8, 2] } });
+        assert_decode_owned(std::ffi::CString::new("owned").expect("Interior nul"));
+    }
+
+    #[test]
+    fn test_decoder_only_codec() {
+        let masked = Masked { mask: 0x0F, value: 3 };
+        let encoded = masked.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, vec![0xF0, 3]);
+        assert_eq!(Masked::decode(&encoded, &mut 0).expect("Decoding failed"), masked);
+    }
+
+    #[test]
+    fn test_skip() {
+        let cached = Cached::<Opaque> {
+            name: "byten".to_string(),
+            name_length: 5,
+            lookups: std::cell::Cell::new(12),
+            marker: std::marker::PhantomData,
+        };
+        let encoded = cached.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, b"\x05byten");
+        assert_eq!(cached.measure().expect("Measuring failed"), encoded.len());
+        let decoded = Cached::<Opaque>::decode(&encoded, &mut 0).expect("Decoding failed");
+        assert_eq!(decoded.name_length, 5);
+        assert_eq!(decoded.lookups.get(), 0);
+        assert_eq!(Cached::<Opaque>::decode_from(&mut encoded.as_slice()).expect("Reading failed"), decoded);
+
+        let stamped = Stamped(7, Some(Box::new(Date { day: 1, month: 1, year: 2000 })), 9);
+        let encoded = stamped.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, vec![0, 0, 0, 7, 9]);
+        assert_eq!(Stamped::measure_fixed(), encoded.len());
+        assert_eq!(Stamped::decode(&encoded, &mut 0).expect("Decoding failed"), Stamped(7, None, 9));
+
+        let tick = Event::Tick { count: 3, fresh: false };
+        let encoded = tick.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, vec![1, 3]);
+        assert_eq!(Event::decode(&encoded, &mut 0).expect("Decoding failed"), Event::Tick { count: 3, fresh: true });
+        let tock = Event::Tock("local".to_string());
+        assert_eq!(tock.encode_to_vec().expect("Encoding failed"), vec![2]);
+        assert_eq!(Event::decode_from(&mut [2].as_slice()).expect("Reading failed"), Event::Tock(String::new()));
+    }
+
+    #[test]
+    fn test_magic() {
+        let header = ImageHeader { width: 640, height: 480, version: () };
+        let expected_encoded = vec![
+            0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n',
+            b'I', b'H', b'D', b'R', 0x00, 0x00, 0x02, 0x80,
+            0x00, 0x00, 0x01, 0xe0,
+            0x00, 0x02,
+        ];
+
+        let encoded = header.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, expected_encoded);
+        assert_eq!(ImageHeader::measure_fixed(), expected_encoded.len());
+        assert_eq!(ImageHeader::decode(&encoded, &mut 0).expect("Decoding failed"), header);
+        assert_eq!(ImageHeader::decode_from(&mut encoded.as_slice()).expect("Reading failed"), header);
+
+        let mut invalid = expected_encoded.clone();
+        invalid[1] = b'J';
+        let error = ImageHeader::decode(&invalid, &mut 0).expect_err("Wrong signature decoded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::MagicMismatch));
+        assert_eq!(error.to_string(), "Magic or constant mismatch in ImageHeader at offset 0");
+
+        let mut invalid = expected_encoded.clone();
+        invalid[11] = b'r';
+        let error = ImageHeader::decode_from(&mut invalid.as_slice()).expect_err("Wrong chunk type decoded");
+        assert_eq!(error.path().map(ToString::to_string).as_deref(), Some("ImageHeader.width"));
+
+        let mut invalid = expected_encoded.clone();
+        invalid[21] = 3;
+        let error = ImageHeader::decode(&invalid, &mut 0).expect_err("Wrong version decoded");
+        assert_eq!(error.to_string(), "Magic or constant mismatch in ImageHeader.version at offset 20");
+
+        let signal = Signal::Stop(4);
+        let encoded = signal.encode_to_vec().expect("Encoding failed");
+        assert_eq!(encoded, b"SG\x02\x04");
+        assert_eq!(signal.measure().expect("Measuring failed"), encoded.len());
+        assert_eq!(Signal::decode(&encoded, &mut 0).expect("Decoding failed"), signal);
+        let error = Signal::decode(b"SX\x01", &mut 0).expect_err("Wrong signature decoded");
+        assert!(matches!(error.kind(), byten::DecodeErrorKind::MagicMismatch));
+    }
 }
@@ -0,0 +1,9 @@
+use byten::Encode;
+
+#[derive(Encode)]
+struct Header {
+    #[byten(byten::prim::U32BE, byten::prim::U32LE)]
+    length: u32,
+}
+
+fn main() {}
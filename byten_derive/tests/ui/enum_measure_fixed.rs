@@ -0,0 +1,10 @@
+use byten::{Encode, MeasureFixed};
+
+#[derive(Encode, MeasureFixed)]
+#[repr(u8)]
+enum Shape {
+    Circle = 1,
+    Square(u8) = 2,
+}
+
+fn main() {}
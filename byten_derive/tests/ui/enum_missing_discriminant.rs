@@ -0,0 +1,10 @@
+use byten::Encode;
+
+#[derive(Encode)]
+#[repr(u8)]
+enum Shape {
+    Circle = 1,
+    Square(u8),
+}
+
+fn main() {}
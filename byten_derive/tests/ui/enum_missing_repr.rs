@@ -0,0 +1,9 @@
+use byten::Decode;
+
+#[derive(Decode)]
+enum Shape {
+    Circle = 1,
+    Square = 2,
+}
+
+fn main() {}
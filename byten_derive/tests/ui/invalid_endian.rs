@@ -0,0 +1,9 @@
+use byten::Encode;
+
+#[derive(Encode)]
+#[byten(endian = "middle")]
+struct Header {
+    length: u32,
+}
+
+fn main() {}
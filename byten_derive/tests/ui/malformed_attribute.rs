@@ -0,0 +1,9 @@
+use byten::Encode;
+
+#[derive(Encode)]
+struct Header {
+    #[byten]
+    length: u8,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use byten::Decode;
+
+#[derive(Decode)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use byten::Encode;
+
+#[derive(Encode)]
+#[byten(max_dept = 4)]
+struct Node {
+    value: u8,
+}
+
+fn main() {}
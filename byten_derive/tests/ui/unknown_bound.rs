@@ -0,0 +1,9 @@
+use byten::Encode;
+
+#[derive(Encode)]
+#[byten(bound(serialize = "T: Encode"))]
+struct Packet<T> {
+    payload: T,
+}
+
+fn main() {}